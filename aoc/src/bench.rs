@@ -0,0 +1,162 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    fs,
+    path::Path,
+    time::Duration,
+};
+
+use aoc_common::{
+    parse::ParseError,
+    solution::{Options, Part},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::days::Runner;
+
+/// Timings over a number of iterations, in nanoseconds so they can be saved as a baseline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub min_ns: u128,
+    pub median_ns: u128,
+    pub p95_ns: u128,
+}
+
+impl Stats {
+    /// Nearest rank percentiles of `samples`, which must not be empty
+    pub fn new(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let percentile =
+            |p: f64| sorted[((p * sorted.len() as f64).ceil() as usize).max(1) - 1].as_nanos();
+        Stats {
+            min_ns: sorted[0].as_nanos(),
+            median_ns: percentile(0.5),
+            p95_ns: percentile(0.95),
+        }
+    }
+}
+
+/// Stats for parsing and each part of a day, keyed by "parse", "part 1" and "part 2"
+pub type DayStats = BTreeMap<String, Stats>;
+
+/// Saved results to compare later runs against, keyed by day
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    pub days: BTreeMap<u8, DayStats>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|err| format!("Could not read {}: {err}", path.display()))?;
+        serde_json::from_str(&contents)
+            .map_err(|err| format!("Invalid baseline {}: {err}", path.display()))
+    }
+
+    /// Adds `days` to the baseline at `path`, replacing any previous results for them
+    pub fn save(path: &Path, days: &BTreeMap<u8, DayStats>) -> Result<(), String> {
+        let mut baseline = if path.exists() {
+            Baseline::load(path)?
+        } else {
+            Baseline::default()
+        };
+        baseline
+            .days
+            .extend(days.iter().map(|(&day, stats)| (day, stats.clone())));
+        let contents = serde_json::to_string_pretty(&baseline).expect("baselines are serialisable");
+        fs::write(path, contents)
+            .map_err(|err| format!("Could not write {}: {err}", path.display()))
+    }
+}
+
+/// Parses and runs both parts `iterations` times, timing each separately
+pub fn bench(run: Runner, input: &str, iterations: usize) -> Result<DayStats, ParseError> {
+    let mut samples: BTreeMap<String, Vec<Duration>> = BTreeMap::new();
+    for _ in 0..iterations.max(1) {
+        let report = run(input, &Part::BOTH, &Options::default())?;
+        samples
+            .entry("parse".to_string())
+            .or_default()
+            .push(report.parse_elapsed);
+        for result in report.parts {
+            samples
+                .entry(format!("part {}", result.part))
+                .or_default()
+                .push(result.elapsed);
+        }
+    }
+    Ok(samples
+        .iter()
+        .map(|(stage, samples)| (stage.clone(), Stats::new(samples)))
+        .collect())
+}
+
+/// A row per day and stage, with the change in median from the baseline when there is one
+pub struct Table<'a> {
+    pub days: &'a BTreeMap<u8, DayStats>,
+    pub baseline: Option<&'a Baseline>,
+}
+
+fn ns(ns: u128) -> String {
+    format!("{:.2?}", Duration::from_nanos(ns as u64))
+}
+
+impl Display for Table<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>3}  {:<6}  {:>12}  {:>12}  {:>12}",
+            "day", "stage", "min", "median", "p95"
+        )?;
+        if self.baseline.is_some() {
+            write!(f, "  {:>12}  {:>8}", "base median", "change")?;
+        }
+        writeln!(f)?;
+
+        for (day, stages) in self.days {
+            for (stage, stats) in stages {
+                write!(
+                    f,
+                    "{day:>3}  {stage:<6}  {:>12}  {:>12}  {:>12}",
+                    ns(stats.min_ns),
+                    ns(stats.median_ns),
+                    ns(stats.p95_ns)
+                )?;
+                if let Some(baseline) = self.baseline {
+                    match baseline.days.get(day).and_then(|stages| stages.get(stage)) {
+                        Some(base) => {
+                            let change = (stats.median_ns as f64 / base.median_ns.max(1) as f64
+                                - 1.0)
+                                * 100.0;
+                            write!(f, "  {:>12}  {change:>+7.1}%", ns(base.median_ns))?;
+                        }
+                        None => write!(f, "  {:>12}  {:>8}", "-", "-")?,
+                    }
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_rank_percentiles() {
+        let samples: Vec<Duration> = (1..=20).rev().map(Duration::from_nanos).collect();
+        assert_eq!(
+            Stats::new(&samples),
+            Stats {
+                min_ns: 1,
+                median_ns: 10,
+                p95_ns: 19,
+            }
+        );
+        let one = Stats::new(&[Duration::from_nanos(7)]);
+        assert_eq!((one.min_ns, one.median_ns, one.p95_ns), (7, 7, 7));
+    }
+}
@@ -0,0 +1,102 @@
+use std::io::Write;
+
+use aoc_common::{
+    image::{Export, ExportError},
+    parse::ParseError,
+    solution::{analyse, export, run, visualise, Options, Part, Report},
+};
+
+pub type Runner = fn(&str, &[Part], &Options) -> Result<Report, ParseError>;
+pub type Analyser = fn(&str, &Options) -> Result<Option<String>, ParseError>;
+pub type Visualiser = fn(&str, &Options) -> Result<(), ParseError>;
+pub type Exporter = fn(&str, usize, &Options, &Export, &mut dyn Write) -> Result<(), ExportError>;
+
+pub const DAYS: std::ops::RangeInclusive<u8> = 1..=16;
+
+pub fn runner(day: u8) -> Option<Runner> {
+    Some(match day {
+        1 => run::<day_1::Day1>,
+        2 => run::<day_2::Day2>,
+        3 => run::<day_3::Day3>,
+        4 => run::<day_4::Day4>,
+        5 => run::<day_5::Day5>,
+        6 => run::<day_6::Day6>,
+        7 => run::<day_7::Day7>,
+        8 => run::<day_8::Day8>,
+        9 => run::<day_9::Day9>,
+        10 => run::<day_10::Day10>,
+        11 => run::<day_11::Day11>,
+        12 => run::<day_12::Day12>,
+        13 => run::<day_13::Day13>,
+        14 => run::<day_14::Day14>,
+        15 => run::<day_15::Day15>,
+        16 => run::<day_16::Day16>,
+        _ => return None,
+    })
+}
+
+pub fn analyser(day: u8) -> Option<Analyser> {
+    Some(match day {
+        1 => analyse::<day_1::Day1>,
+        2 => analyse::<day_2::Day2>,
+        3 => analyse::<day_3::Day3>,
+        4 => analyse::<day_4::Day4>,
+        5 => analyse::<day_5::Day5>,
+        6 => analyse::<day_6::Day6>,
+        7 => analyse::<day_7::Day7>,
+        8 => analyse::<day_8::Day8>,
+        9 => analyse::<day_9::Day9>,
+        10 => analyse::<day_10::Day10>,
+        11 => analyse::<day_11::Day11>,
+        12 => analyse::<day_12::Day12>,
+        13 => analyse::<day_13::Day13>,
+        14 => analyse::<day_14::Day14>,
+        15 => analyse::<day_15::Day15>,
+        16 => analyse::<day_16::Day16>,
+        _ => return None,
+    })
+}
+
+pub fn visualiser(day: u8) -> Option<Visualiser> {
+    Some(match day {
+        1 => visualise::<day_1::Day1>,
+        2 => visualise::<day_2::Day2>,
+        3 => visualise::<day_3::Day3>,
+        4 => visualise::<day_4::Day4>,
+        5 => visualise::<day_5::Day5>,
+        6 => visualise::<day_6::Day6>,
+        7 => visualise::<day_7::Day7>,
+        8 => visualise::<day_8::Day8>,
+        9 => visualise::<day_9::Day9>,
+        10 => visualise::<day_10::Day10>,
+        11 => visualise::<day_11::Day11>,
+        12 => visualise::<day_12::Day12>,
+        13 => visualise::<day_13::Day13>,
+        14 => visualise::<day_14::Day14>,
+        15 => visualise::<day_15::Day15>,
+        16 => visualise::<day_16::Day16>,
+        _ => return None,
+    })
+}
+
+pub fn exporter(day: u8) -> Option<Exporter> {
+    Some(match day {
+        1 => export::<day_1::Day1>,
+        2 => export::<day_2::Day2>,
+        3 => export::<day_3::Day3>,
+        4 => export::<day_4::Day4>,
+        5 => export::<day_5::Day5>,
+        6 => export::<day_6::Day6>,
+        7 => export::<day_7::Day7>,
+        8 => export::<day_8::Day8>,
+        9 => export::<day_9::Day9>,
+        10 => export::<day_10::Day10>,
+        11 => export::<day_11::Day11>,
+        12 => export::<day_12::Day12>,
+        13 => export::<day_13::Day13>,
+        14 => export::<day_14::Day14>,
+        15 => export::<day_15::Day15>,
+        16 => export::<day_16::Day16>,
+        _ => return None,
+    })
+}
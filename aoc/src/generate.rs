@@ -0,0 +1,674 @@
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+use aoc_common::{
+    grid::{Grid2D, Pos},
+    point::Point,
+};
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+/// Writes a valid puzzle input in a day's format, scaled by the given size
+pub type Generate = fn(usize, &mut StdRng) -> String;
+
+pub struct Generator {
+    /// What the size counts, e.g. "elves"
+    pub unit: &'static str,
+    /// Roughly the size of a real puzzle input
+    pub default_size: usize,
+    pub generate: Generate,
+}
+
+const GENERATORS: [Generator; 16] = [
+    Generator {
+        unit: "elves",
+        default_size: 250,
+        generate: day_1,
+    },
+    Generator {
+        unit: "rounds",
+        default_size: 2500,
+        generate: day_2,
+    },
+    Generator {
+        unit: "groups of three elves",
+        default_size: 100,
+        generate: day_3,
+    },
+    Generator {
+        unit: "pairs",
+        default_size: 1000,
+        generate: day_4,
+    },
+    Generator {
+        unit: "instructions",
+        default_size: 500,
+        generate: day_5,
+    },
+    Generator {
+        unit: "characters",
+        default_size: 4096,
+        generate: day_6,
+    },
+    Generator {
+        unit: "directories",
+        default_size: 200,
+        generate: day_7,
+    },
+    Generator {
+        unit: "trees per side",
+        default_size: 99,
+        generate: day_8,
+    },
+    Generator {
+        unit: "motions",
+        default_size: 2000,
+        generate: day_9,
+    },
+    Generator {
+        unit: "instructions",
+        default_size: 140,
+        generate: day_10,
+    },
+    Generator {
+        unit: "monkeys",
+        default_size: 8,
+        generate: day_11,
+    },
+    Generator {
+        unit: "rows",
+        default_size: 41,
+        generate: day_12,
+    },
+    Generator {
+        unit: "pairs",
+        default_size: 150,
+        generate: day_13,
+    },
+    Generator {
+        unit: "rock paths",
+        default_size: 150,
+        generate: day_14,
+    },
+    Generator {
+        unit: "sensors per side of the search area",
+        default_size: 4,
+        generate: day_15,
+    },
+    Generator {
+        unit: "valves",
+        default_size: 60,
+        generate: day_16,
+    },
+];
+
+pub fn generator(day: u8) -> Option<&'static Generator> {
+    GENERATORS.get(usize::from(day).checked_sub(1)?)
+}
+
+/// The same size and seed always give the same input
+pub fn generate(generator: &Generator, size: usize, seed: u64) -> String {
+    (generator.generate)(size, &mut StdRng::seed_from_u64(seed))
+}
+
+fn day_1(elves: usize, rng: &mut StdRng) -> String {
+    (0..elves.max(1))
+        .map(|_| {
+            let items = rng.gen_range(1..=15);
+            (0..items)
+                .map(|_| format!("{}\n", rng.gen_range(1000..=60000)))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn day_2(rounds: usize, rng: &mut StdRng) -> String {
+    (0..rounds.max(1))
+        .map(|_| {
+            let opponent = *['A', 'B', 'C'].choose(rng).unwrap();
+            let response = *['X', 'Y', 'Z'].choose(rng).unwrap();
+            format!("{opponent} {response}\n")
+        })
+        .collect()
+}
+
+/// Each elf's items come from their own third of the alphabet, so the compartments share
+/// exactly one item and the group shares exactly one badge
+fn day_3(groups: usize, rng: &mut StdRng) -> String {
+    let mut items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    let mut out = String::new();
+    for _ in 0..groups.max(1) {
+        items.shuffle(rng);
+        let (&badge, rest) = items.split_first().unwrap();
+        for pool in rest.chunks(17) {
+            let (&shared, pool) = pool.split_first().unwrap();
+            let (left_pool, right_pool) = pool.split_at(8);
+            let half = rng.gen_range(4..=16);
+            let mut compartments = [left_pool, right_pool].map(|pool| {
+                let mut compartment: Vec<char> =
+                    (1..half).map(|_| *pool.choose(rng).unwrap()).collect();
+                compartment.push(shared);
+                compartment
+            });
+            compartments[rng.gen_range(0..2)][0] = badge;
+            for compartment in compartments.iter_mut() {
+                compartment.shuffle(rng);
+                out.extend(compartment.iter());
+            }
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn day_4(pairs: usize, rng: &mut StdRng) -> String {
+    let section = |rng: &mut StdRng| {
+        let start = rng.gen_range(1..=99);
+        (start, rng.gen_range(start..=99))
+    };
+    (0..pairs.max(1))
+        .map(|_| {
+            let (a, b) = section(rng);
+            let (c, d) = section(rng);
+            format!("{a}-{b},{c}-{d}\n")
+        })
+        .collect()
+}
+
+/// Moves never empty a stack, so there is always a crate on top of each at the end
+fn day_5(instructions: usize, rng: &mut StdRng) -> String {
+    let stacks: Vec<Vec<char>> = (0..9)
+        .map(|_| {
+            (0..rng.gen_range(2..=8))
+                .map(|_| rng.gen_range('A'..='Z'))
+                .collect()
+        })
+        .collect();
+    let height = stacks.iter().map(Vec::len).max().unwrap();
+
+    let mut out = String::new();
+    for level in (0..height).rev() {
+        let row: Vec<String> = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(c) => format!("[{c}]"),
+                None => "   ".to_string(),
+            })
+            .collect();
+        out += &row.join(" ");
+        out.push('\n');
+    }
+    let labels: Vec<String> = (1..=stacks.len()).map(|i| format!(" {i} ")).collect();
+    out += &labels.join(" ");
+    out += "\n\n";
+
+    let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+    for _ in 0..instructions {
+        let movable: Vec<usize> = (0..heights.len()).filter(|&i| heights[i] > 1).collect();
+        let origin = *movable.choose(rng).unwrap();
+        let dest = (origin + rng.gen_range(1..heights.len())) % heights.len();
+        let qty = rng.gen_range(1..heights[origin]).min(rng.gen_range(1..=20));
+        heights[origin] -= qty;
+        heights[dest] += qty;
+        out += &format!("move {qty} from {} to {}\n", origin + 1, dest + 1);
+    }
+    out
+}
+
+/// Noise from 3 letters can't hold a start-of-packet marker, and from 13 can't hold a
+/// start-of-message marker, which is placed at the end
+fn day_6(characters: usize, rng: &mut StdRng) -> String {
+    let mut letters: Vec<char> = ('a'..='z').collect();
+    letters.shuffle(rng);
+    let noise = characters.max(17) - 17;
+    let mut stream: String = (0..noise)
+        .map(|i| {
+            let alphabet = if i < noise / 2 { 3 } else { 13 };
+            letters[rng.gen_range(0..alphabet)]
+        })
+        .collect();
+    let mut marker = letters[..14].to_vec();
+    marker.shuffle(rng);
+    stream.extend(marker);
+    stream.extend((0..3).map(|_| letters[rng.gen_range(0..3)]));
+    stream.push('\n');
+    stream
+}
+
+#[derive(Default)]
+struct Directory {
+    dirs: Vec<(String, usize)>,
+    files: Vec<(String, usize)>,
+}
+
+impl Directory {
+    fn has(&self, name: &str) -> bool {
+        self.dirs.iter().chain(&self.files).any(|(n, _)| n == name)
+    }
+}
+
+fn file_name(rng: &mut StdRng) -> String {
+    let mut name: String = (0..rng.gen_range(1..=8))
+        .map(|_| rng.gen_range('a'..='z'))
+        .collect();
+    if rng.gen_bool(0.5) {
+        name.push('.');
+        name.extend((0..3).map(|_| rng.gen_range('a'..='z')));
+    }
+    name
+}
+
+/// `$ ls` of directory `i`, then the same for each subdirectory
+fn list(tree: &[Directory], i: usize, rng: &mut StdRng, out: &mut String) {
+    *out += "$ ls\n";
+    let mut entries: Vec<String> = tree[i]
+        .dirs
+        .iter()
+        .map(|(name, _)| format!("dir {name}"))
+        .chain(
+            tree[i]
+                .files
+                .iter()
+                .map(|(name, size)| format!("{size} {name}")),
+        )
+        .collect();
+    entries.shuffle(rng);
+    for entry in entries {
+        *out += &entry;
+        out.push('\n');
+    }
+    for (name, child) in &tree[i].dirs {
+        *out += &format!("$ cd {name}\n");
+        list(tree, *child, rng, out);
+        *out += "$ cd ..\n";
+    }
+}
+
+/// The disk is between 45 and 65 million full, so part 2 always has to delete something
+fn day_7(directories: usize, rng: &mut StdRng) -> String {
+    let mut tree = vec![Directory::default()];
+    for i in 1..directories.max(1) {
+        let parent = rng.gen_range(0..i);
+        let name = loop {
+            let name = file_name(rng);
+            if !tree[parent].has(&name) {
+                break name;
+            }
+        };
+        tree[parent].dirs.push((name, i));
+        tree.push(Directory::default());
+    }
+
+    let mut weights = vec![];
+    for (i, dir) in tree.iter_mut().enumerate() {
+        for _ in 0..rng.gen_range(usize::from(i == 0)..=4) {
+            let name = loop {
+                let name = file_name(rng);
+                if !dir.has(&name) {
+                    break name;
+                }
+            };
+            // squared so there are a few large files among many small ones
+            let weight = rng.gen_range(1..=1000_usize).pow(2);
+            weights.push(weight);
+            dir.files.push((name, weight));
+        }
+    }
+    let used = rng.gen_range(45_000_000..=65_000_000);
+    let total_weight: usize = weights.iter().sum();
+    for dir in tree.iter_mut() {
+        for (_, size) in dir.files.iter_mut() {
+            *size = (*size * used / total_weight).max(1);
+        }
+    }
+
+    let mut out = "$ cd /\n".to_string();
+    list(&tree, 0, rng, &mut out);
+    out
+}
+
+fn day_8(side: usize, rng: &mut StdRng) -> String {
+    (0..side.max(1))
+        .map(|_| {
+            let mut row: String = (0..side.max(1))
+                .map(|_| char::from(b'0' + rng.gen_range(0..=9)))
+                .collect();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+fn day_9(motions: usize, rng: &mut StdRng) -> String {
+    (0..motions.max(1))
+        .map(|_| {
+            let direction = *['U', 'D', 'L', 'R'].choose(rng).unwrap();
+            format!("{direction} {}\n", rng.gen_range(1..=20))
+        })
+        .collect()
+}
+
+/// The register jumps between positions on the screen, as in real programs
+fn day_10(instructions: usize, rng: &mut StdRng) -> String {
+    let mut register = 1;
+    (0..instructions.max(1))
+        .map(|_| {
+            let v = rng.gen_range(0..40) - register;
+            if v == 0 || rng.gen_bool(0.3) {
+                "noop\n".to_string()
+            } else {
+                register += v;
+                format!("addx {v}\n")
+            }
+        })
+        .collect()
+}
+
+/// Operations never multiply by more than 3, so worry levels can't overflow in part 1 where
+/// they're divided by 3 after each inspection. Once the product of the divisors would pass
+/// 2^64, further monkeys test divisibility by 1 to keep part 2's modulus in range.
+fn day_11(monkeys: usize, rng: &mut StdRng) -> String {
+    const PRIMES: [u128; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+    let monkeys = monkeys.max(2);
+    let mut product: u128 = 1;
+    (0..monkeys)
+        .map(|i| {
+            let items: Vec<String> = (0..rng.gen_range(1..=6))
+                .map(|_| rng.gen_range(50..100).to_string())
+                .collect();
+            let operation = if rng.gen_bool(0.5) {
+                format!("old + {}", rng.gen_range(1..=8))
+            } else {
+                format!("old * {}", rng.gen_range(2..=3))
+            };
+            let prime = *PRIMES.choose(rng).unwrap();
+            let div_by = if product * prime <= u128::from(u64::MAX) {
+                product *= prime;
+                prime
+            } else {
+                1
+            };
+            let others: Vec<usize> = (0..monkeys).filter(|&j| j != i).collect();
+            let (pass, fail) = match others.choose_multiple(rng, 2).collect::<Vec<_>>()[..] {
+                [&pass, &fail] => (pass, fail),
+                _ => (others[0], others[0]),
+            };
+            format!(
+                "Monkey {i}:\n  Starting items: {}\n  Operation: new = {operation}\n  Test: divisible by {div_by}\n    If true: throw to monkey {pass}\n    If false: throw to monkey {fail}\n",
+                items.join(", ")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn climbable(heights: &Grid2D<u8>, start: Pos, end: Pos) -> bool {
+    let mut seen = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(pos) = queue.pop_front() {
+        if pos == end {
+            return true;
+        }
+        for next in heights.neighbours4(pos) {
+            if heights[next] <= heights[pos] + 1 && seen.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    false
+}
+
+/// Heights fall away evenly from E, so it can always be reached, with random cliffs to climb
+/// around. Grids are at least 12 by 48 so there is room to fall all the way from z to a.
+fn day_12(rows: usize, rng: &mut StdRng) -> String {
+    let rows = rows.max(12);
+    let cols = rows * 4;
+    let end = (rng.gen_range(0..rows), rng.gen_range(0..cols));
+    let distance = |(i, j): Pos| i.abs_diff(end.0) + j.abs_diff(end.1);
+    let furthest = distance((0, 0))
+        .max(distance((rows - 1, 0)))
+        .max(distance((0, cols - 1)))
+        .max(distance((rows - 1, cols - 1)));
+    let scale = furthest / 25;
+    let mut slope = Grid2D::filled(rows, cols, 0);
+    for pos in slope.positions() {
+        slope[pos] = ((furthest - distance(pos)) / scale).min(25) as u8;
+    }
+    let lowest: Vec<Pos> = slope
+        .iter()
+        .filter(|(_, &height)| height == 0)
+        .map(|(pos, _)| pos)
+        .collect();
+    let start = *lowest.choose(rng).unwrap();
+
+    // falls back to the bare slope if the cliffs keep blocking the way
+    let mut heights = slope.clone();
+    for _ in 0..10 {
+        let mut cliffs = slope.clone();
+        for pos in slope.positions() {
+            if pos != start && pos != end && rng.gen_bool(0.1) {
+                cliffs[pos] = (cliffs[pos] + rng.gen_range(2..=5)).min(25);
+            }
+        }
+        if climbable(&cliffs, start, end) {
+            heights = cliffs;
+            break;
+        }
+    }
+
+    let mut out = String::new();
+    for (pos, &height) in heights.iter() {
+        out.push(if pos == start {
+            'S'
+        } else if pos == end {
+            'E'
+        } else {
+            char::from(b'a' + height)
+        });
+        if pos.1 == cols - 1 {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn packet(rng: &mut StdRng, depth: usize) -> String {
+    let items: Vec<String> = (0..rng.gen_range(0..=5))
+        .map(|_| {
+            if depth < 4 && rng.gen_bool(0.3) {
+                packet(rng, depth + 1)
+            } else {
+                rng.gen_range(0..=10).to_string()
+            }
+        })
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+fn day_13(pairs: usize, rng: &mut StdRng) -> String {
+    (0..pairs.max(1))
+        .map(|_| format!("{}\n{}\n", packet(rng, 0), packet(rng, 0)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Paths of horizontal and vertical lines in a cave as deep as there are paths and twice as wide.
+/// They start a fifth of the way down and are sparse enough for sand to spread out and fall past.
+fn day_14(paths: usize, rng: &mut StdRng) -> String {
+    let depth = paths.max(10) as i32;
+    let max_len = depth / 10 + 2;
+    (0..paths.max(1))
+        .map(|_| {
+            let mut x = 500 + rng.gen_range(-depth..=depth);
+            let mut y = rng.gen_range(depth / 5..=depth);
+            let mut points = vec![format!("{x},{y}")];
+            let horizontal = rng.gen_bool(0.5);
+            for i in 0..rng.gen_range(1..=5) {
+                let step = rng.gen_range(1..=max_len) * if rng.gen_bool(0.5) { 1 } else { -1 };
+                if (i % 2 == 0) == horizontal {
+                    x += step;
+                } else {
+                    y = (y + step).max(1);
+                }
+                points.push(format!("{x},{y}"));
+            }
+            points.join(" -> ") + "\n"
+        })
+        .collect()
+}
+
+/// Sensors and their closest beacons covering every position of a `max_coord` square except
+/// the distress beacon, which is also returned.
+///
+/// Rotated 45 degrees, sensor ranges are squares. They're laid out in a grid overlapping by
+/// their edges, with beacons on the shared corners, so no beacon is closer to another sensor.
+/// The square around the distress beacon is replaced by four half size ones, each touching it.
+fn sensor_grid(per_side: usize, max_coord: i32, rng: &mut StdRng) -> (Point, Vec<(Point, Point)>) {
+    // tuning_frequency expects the distress beacon away from x = 0
+    let (px, py) = (rng.gen_range(1..=max_coord), rng.gen_range(0..=max_coord));
+    let r = (max_coord / per_side.max(1) as i32).max(2);
+    let r = r + r % 2;
+    let unrotate = |u: i32, v: i32| Point::new(px + (u + v) / 2, py + (u - v) / 2);
+
+    let mut sensors = vec![];
+    let (min, max) = (-(max_coord / r) - 2, max_coord / r + 2);
+    for k in min..=max {
+        for l in min..=max {
+            let (u, v) = (2 * r * k, 2 * r * l);
+            let sensor = unrotate(u, v);
+            let nearest = Point::new(sensor.x.clamp(0, max_coord), sensor.y.clamp(0, max_coord));
+            let reaches_area = sensor.dist_to(nearest) <= r;
+            if (k, l) == (0, 0) || !reaches_area {
+                continue;
+            }
+            let corner = *[(r, r), (r, -r), (-r, r), (-r, -r)].choose(rng).unwrap();
+            sensors.push((sensor, unrotate(u + corner.0, v + corner.1)));
+        }
+    }
+    for (u, v, beacon) in [(r, 0, 1), (0, r, 1), (-r, 0, -1), (0, -r, -1)] {
+        sensors.push((unrotate(u, v), Point::new(px + beacon, py)));
+    }
+    sensors.shuffle(rng);
+    (Point::new(px, py), sensors)
+}
+
+fn day_15(per_side: usize, rng: &mut StdRng) -> String {
+    let (_, sensors) = sensor_grid(per_side, 4_000_000, rng);
+    sensors
+        .iter()
+        .map(|(sensor, beacon)| {
+            format!(
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+                sensor.x, sensor.y, beacon.x, beacon.y
+            )
+        })
+        .collect()
+}
+
+/// A connected network with a quarter of the valves working, other than AA, and at least two
+/// so that both you and the elephant have one to head for
+fn day_16(valves: usize, rng: &mut StdRng) -> String {
+    let valves = valves.clamp(3, 26 * 26);
+    let mut names = vec!["AA".to_string()];
+    while names.len() < valves {
+        let name: String = (0..2).map(|_| rng.gen_range('A'..='Z')).collect();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    let mut tunnels: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); valves];
+    let mut connect = |a: usize, b: usize| {
+        if a != b {
+            tunnels[a].insert(b);
+            tunnels[b].insert(a);
+        }
+    };
+    for i in 1..valves {
+        connect(i, rng.gen_range(0..i));
+    }
+    for _ in 0..valves / 2 {
+        connect(rng.gen_range(0..valves), rng.gen_range(0..valves));
+    }
+
+    let mut rates = vec![0; valves];
+    let working: Vec<usize> = (1..valves).collect();
+    for &i in working.choose_multiple(rng, (valves / 4).max(2)) {
+        rates[i] = rng.gen_range(1..=25);
+    }
+
+    let mut order: Vec<usize> = (0..valves).collect();
+    order.shuffle(rng);
+    order
+        .into_iter()
+        .map(|i| {
+            let mut to: Vec<&str> = tunnels[i].iter().map(|&j| names[j].as_str()).collect();
+            to.shuffle(rng);
+            let tunnels = match to[..] {
+                [one] => format!("tunnel leads to valve {one}"),
+                _ => format!("tunnels lead to valves {}", to.join(", ")),
+            };
+            format!("Valve {} has flow rate={}; {tunnels}\n", names[i], rates[i])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::{Options, Part};
+
+    use crate::days::{self, DAYS};
+
+    use super::*;
+
+    #[test]
+    fn generated_inputs_are_solvable() {
+        for day in DAYS {
+            let generator = generator(day).unwrap();
+            // day 15 searches millions of positions at any size, see sensors_cover_all_but_one
+            let parts: &[Part] = if day == 15 { &[] } else { &Part::BOTH };
+            for seed in 0..3 {
+                let input = generate(generator, 8, seed);
+                assert_eq!(input, generate(generator, 8, seed), "day {day}");
+                let run = days::runner(day).unwrap();
+                if let Err(err) = run(&input, parts, &Options::default()) {
+                    panic!("day {day} seed {seed}: {err}\n{input}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sensors_cover_all_but_one() {
+        for seed in 0..10 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let (distress, sensors) = sensor_grid(3, 20, &mut rng);
+            let data: Vec<day_15::SensorData> = sensors
+                .iter()
+                .map(|&(sensor_pos, beacon_pos)| day_15::SensorData {
+                    sensor_pos,
+                    beacon_pos,
+                    empty_zone_radius: sensor_pos.dist_to(beacon_pos),
+                })
+                .collect();
+            for d in &data {
+                assert!(data
+                    .iter()
+                    .all(|other| d.sensor_pos.dist_to(other.beacon_pos) >= d.empty_zone_radius));
+            }
+            for x in 0..=20 {
+                for y in 0..=20 {
+                    let pos = Point::new(x, y);
+                    let covered = data
+                        .iter()
+                        .any(|d| d.sensor_pos.dist_to(pos) <= d.empty_zone_radius);
+                    assert_eq!(covered, pos != distress, "seed {seed} ({x}, {y})");
+                }
+            }
+            assert_eq!(
+                day_15::tuning_frequency(&data, 20),
+                distress.x as i128 * 4_000_000 + distress.y as i128
+            );
+        }
+    }
+}
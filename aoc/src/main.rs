@@ -0,0 +1,398 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use aoc_common::{
+    image::{Export, Format, Palette},
+    input::{self, InputSource},
+    solution::{Options, Part},
+};
+use clap::{Args, Parser, Subcommand};
+
+mod bench;
+mod days;
+mod generate;
+mod output;
+mod verify;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a day's solution, printing each answer and how long it took
+    Run {
+        #[arg(long)]
+        day: u8,
+        /// Run only part 1 or 2, rather than both
+        #[arg(long)]
+        part: Option<Part>,
+        /// Puzzle input path, or `-` for stdin. Defaults to `$AOC_INPUT`, then `day N/input`
+        #[arg(long)]
+        input: Option<InputSource>,
+        /// Also print the day's analysis of the input, if it has one
+        #[arg(long, conflicts_with = "json")]
+        analyse: bool,
+        /// Also print the day's visualisation, if it has one
+        #[arg(long, conflicts_with = "json")]
+        vis: bool,
+        /// Print a JSON object per part instead: day, part, answer, timings and the input's hash
+        #[arg(long)]
+        json: bool,
+        #[command(flatten)]
+        options: DayOptions,
+    },
+    /// Check answers on the example and real inputs against each day's `answers.toml`
+    Verify {
+        /// Only verify this day, rather than all of them
+        #[arg(long)]
+        day: Option<u8>,
+        /// Check the examples and answers given in each day's README instead
+        #[arg(long)]
+        readme: bool,
+    },
+    /// Time parsing and each part separately, reporting min/median/p95 over a number of iterations
+    Bench {
+        /// Only benchmark this day, rather than all of them
+        #[arg(long)]
+        day: Option<u8>,
+        /// Puzzle input path, or `-` for stdin, when benchmarking a single day
+        #[arg(long, requires = "day")]
+        input: Option<InputSource>,
+        #[arg(long, default_value_t = 10)]
+        iterations: usize,
+        /// Compare medians against a baseline previously written with `--save`
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+        /// Save the results as a baseline, adding to the file if it already exists
+        #[arg(long)]
+        save: Option<PathBuf>,
+    },
+    /// Write a random puzzle input in a day's format, for scaling benchmarks and fuzzing parsers
+    Generate {
+        #[arg(long)]
+        day: u8,
+        /// Number of elves, valves, etc. depending on the day. Defaults to about a real input's size
+        #[arg(long)]
+        size: Option<usize>,
+        /// The same seed and size always generate the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Write the input to this path rather than stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Write one of a day's animations as an image: its last frame as a PNG or PPM, or an animated GIF
+    Export {
+        #[arg(long)]
+        day: u8,
+        /// Puzzle input path, or `-` for stdin. Defaults to `$AOC_INPUT`, then `day N/input`
+        #[arg(long)]
+        input: Option<InputSource>,
+        /// Image path. Its extension gives the format unless `--format` is given
+        #[arg(long)]
+        output: PathBuf,
+        /// png, ppm or gif
+        #[arg(long)]
+        format: Option<Format>,
+        /// Which of the day's animations, counting from 0
+        #[arg(long, default_value_t = 0)]
+        animation: usize,
+        /// Pixels along each side of a cell
+        #[arg(long, default_value_t = 4)]
+        scale: usize,
+        /// Colours as `key=rrggbb` pairs, the key being `bg`, `fg` or a character, e.g. `bg=ffffff,#=808080`
+        #[arg(long)]
+        palette: Option<Palette>,
+        /// Stop after this many steps of the animation, rather than at its end
+        #[arg(long)]
+        steps: Option<usize>,
+        /// Steps of the animation between frames of a GIF
+        #[arg(long, default_value_t = 1)]
+        every: usize,
+        /// Hundredths of a second each frame of a GIF is shown for
+        #[arg(long, default_value_t = 5)]
+        delay: u16,
+        #[command(flatten)]
+        options: DayOptions,
+    },
+}
+
+/// Options only some days take, each passed on to the day's solution by name
+#[derive(Args)]
+struct DayOptions {
+    /// Day 1: how many of the elves carrying the most to add up for part 2, rather than 3
+    #[arg(long)]
+    top: Option<usize>,
+    /// Day 2: the game the strategy guide is for, by name (e.g. `rock_paper_scissors_lizard_spock`)
+    /// or as a TOML file like those in `day 2/rust/games`
+    #[arg(long)]
+    game: Option<String>,
+    /// Day 5: the crane both parts rearrange with: 9000, 9001, `limited:<capacity>` or
+    /// `insert:<depth>`
+    #[arg(long)]
+    crane: Option<String>,
+}
+
+impl DayOptions {
+    fn options(self) -> Options {
+        let mut options = Options::default();
+        if let Some(top) = self.top {
+            options.set("top", top);
+        }
+        if let Some(game) = self.game {
+            options.set("game", game);
+        }
+        if let Some(crane) = self.crane {
+            options.set("crane", crane);
+        }
+        options
+    }
+}
+
+fn runner(day: u8) -> Result<days::Runner, String> {
+    days::runner(day).ok_or_else(|| {
+        format!(
+            "No solution for day {day}, expected one of {:?}",
+            days::DAYS
+        )
+    })
+}
+
+fn read_input(day: u8, input: Option<InputSource>) -> Result<String, String> {
+    InputSource::resolve(input, input::default_path(day))
+        .and_then(|source| source.read())
+        .map_err(|err| err.to_string())
+}
+
+fn selected_days(day: Option<u8>) -> Vec<u8> {
+    match day {
+        Some(day) => vec![day],
+        None => days::DAYS.collect(),
+    }
+}
+
+fn run(
+    day: u8,
+    part: Option<Part>,
+    input: Option<InputSource>,
+    analyse: bool,
+    vis: bool,
+    json: bool,
+    options: &Options,
+) -> Result<ExitCode, String> {
+    let run = runner(day)?;
+    let contents = read_input(day, input)?;
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    };
+    let report =
+        run(&contents, &parts, options).map_err(|err| format!("Invalid day {day} input, {err}"))?;
+    if json {
+        for record in output::records(&report, &contents) {
+            println!(
+                "{}",
+                serde_json::to_string(&record).expect("records are serialisable")
+            );
+        }
+    } else {
+        print!("{report}");
+    }
+    if let Some(analyse) = days::analyser(day).filter(|_| analyse) {
+        let analysis =
+            analyse(&contents, options).map_err(|err| format!("Invalid day {day} input, {err}"))?;
+        print!("{}", analysis.unwrap_or_default());
+    }
+    if let Some(visualise) = days::visualiser(day).filter(|_| vis) {
+        visualise(&contents, options).map_err(|err| format!("Invalid day {day} input, {err}"))?;
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn verify(day: Option<u8>, readme: bool) -> Result<ExitCode, String> {
+    let verify = if readme {
+        verify::verify_readme
+    } else {
+        verify::verify
+    };
+    let mut checks = vec![];
+    for day in selected_days(day) {
+        checks.extend(verify(day, runner(day)?));
+    }
+    print!("{}", verify::Table(&checks));
+    if checks.iter().all(|check| check.passed()) {
+        Ok(ExitCode::SUCCESS)
+    } else {
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+fn bench(
+    day: Option<u8>,
+    input: Option<InputSource>,
+    iterations: usize,
+    baseline: Option<PathBuf>,
+    save: Option<PathBuf>,
+) -> Result<ExitCode, String> {
+    let baseline = baseline
+        .map(|path| bench::Baseline::load(&path))
+        .transpose()?;
+
+    let mut results = BTreeMap::new();
+    let mut input = input;
+    for day in selected_days(day) {
+        let run = runner(day)?;
+        let contents = read_input(day, input.take())?;
+        eprintln!("Benchmarking day {day}...");
+        let stats = bench::bench(run, &contents, iterations)
+            .map_err(|err| format!("Invalid day {day} input, {err}"))?;
+        results.insert(day, stats);
+    }
+
+    print!(
+        "{}",
+        bench::Table {
+            days: &results,
+            baseline: baseline.as_ref(),
+        }
+    );
+    if let Some(path) = save {
+        bench::Baseline::save(&path, &results)?;
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn generate(
+    day: u8,
+    size: Option<usize>,
+    seed: u64,
+    output: Option<PathBuf>,
+) -> Result<ExitCode, String> {
+    let generator = generate::generator(day).ok_or_else(|| {
+        format!(
+            "No generator for day {day}, expected one of {:?}",
+            days::DAYS
+        )
+    })?;
+    let size = size.unwrap_or(generator.default_size);
+    let contents = generate::generate(generator, size, seed);
+    match output {
+        Some(path) => {
+            fs::write(&path, contents)
+                .map_err(|err| format!("Could not write {}: {err}", path.display()))?;
+            eprintln!(
+                "Generated day {day} input with {size} {} (seed {seed}) in {}",
+                generator.unit,
+                path.display()
+            );
+        }
+        None => print!("{contents}"),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn export(
+    day: u8,
+    input: Option<InputSource>,
+    output: &Path,
+    animation: usize,
+    options: &Options,
+    export: &Export,
+) -> Result<ExitCode, String> {
+    let exporter = days::exporter(day).ok_or_else(|| {
+        format!(
+            "No solution for day {day}, expected one of {:?}",
+            days::DAYS
+        )
+    })?;
+    let contents = read_input(day, input)?;
+    let mut image = vec![];
+    exporter(&contents, animation, options, export, &mut image)
+        .map_err(|err| format!("Could not export day {day}, {err}"))?;
+    fs::write(output, image)
+        .map_err(|err| format!("Could not write {}: {err}", output.display()))?;
+    eprintln!(
+        "Exported day {day} animation {animation} to {}",
+        output.display()
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+fn main() -> ExitCode {
+    let result = match Cli::parse().command {
+        Command::Run {
+            day,
+            part,
+            input,
+            analyse,
+            vis,
+            json,
+            options,
+        } => run(day, part, input, analyse, vis, json, &options.options()),
+        Command::Verify { day, readme } => verify(day, readme),
+        Command::Bench {
+            day,
+            input,
+            iterations,
+            baseline,
+            save,
+        } => bench(day, input, iterations, baseline, save),
+        Command::Generate {
+            day,
+            size,
+            seed,
+            output,
+        } => generate(day, size, seed, output),
+        Command::Export {
+            day,
+            input,
+            output,
+            format,
+            animation,
+            scale,
+            palette,
+            steps,
+            every,
+            delay,
+            options,
+        } => format
+            .or_else(|| Format::from_path(&output))
+            .ok_or_else(|| {
+                format!(
+                    "Unknown image format for {}, expected png, ppm or gif",
+                    output.display()
+                )
+            })
+            .and_then(|format| {
+                let export_options = Export {
+                    format,
+                    palette: palette.unwrap_or_default(),
+                    scale,
+                    steps,
+                    every,
+                    delay,
+                };
+                export(
+                    day,
+                    input,
+                    &output,
+                    animation,
+                    &options.options(),
+                    &export_options,
+                )
+            }),
+    };
+    result.unwrap_or_else(|err| {
+        eprintln!("{err}");
+        ExitCode::FAILURE
+    })
+}
@@ -0,0 +1,61 @@
+use aoc_common::solution::{Part, Report};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// One part's result, as printed by `run --json` with one object per line
+#[derive(Debug, Serialize)]
+pub struct PartRecord<'a> {
+    pub day: u8,
+    pub part: u8,
+    pub answer: &'a str,
+    pub elapsed_ns: u128,
+    pub parse_elapsed_ns: u128,
+    /// Hex SHA-256 of the input, so results from different inputs aren't compared
+    pub input_sha256: String,
+}
+
+pub fn records<'a>(report: &'a Report, input: &str) -> Vec<PartRecord<'a>> {
+    let input_sha256 = format!("{:x}", Sha256::digest(input.as_bytes()));
+    report
+        .parts
+        .iter()
+        .map(|result| PartRecord {
+            day: report.day,
+            part: match result.part {
+                Part::One => 1,
+                Part::Two => 2,
+            },
+            answer: &result.answer,
+            elapsed_ns: result.elapsed.as_nanos(),
+            parse_elapsed_ns: report.parse_elapsed.as_nanos(),
+            input_sha256: input_sha256.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use aoc_common::solution::PartResult;
+
+    use super::*;
+
+    #[test]
+    fn json_lines() {
+        let report = Report {
+            day: 6,
+            parse_elapsed: Duration::from_nanos(5),
+            parts: vec![PartResult {
+                part: Part::Two,
+                answer: "19".to_string(),
+                elapsed: Duration::from_micros(2),
+            }],
+        };
+        let records = records(&report, "abc");
+        assert_eq!(
+            serde_json::to_string(&records[0]).unwrap(),
+            r#"{"day":6,"part":2,"answer":"19","elapsed_ns":2000,"parse_elapsed_ns":5,"input_sha256":"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"}"#
+        );
+    }
+}
@@ -0,0 +1,313 @@
+use std::{
+    fmt::{self, Display},
+    fs,
+    path::PathBuf,
+    time::Duration,
+};
+
+use aoc_common::{
+    examples,
+    input::{self, InputSource},
+    solution::{Options, Part},
+};
+use serde::Deserialize;
+
+use crate::days::Runner;
+
+/// Recorded answers, read from `day N/answers.toml`
+#[derive(Debug, Default, Deserialize)]
+pub struct Answers {
+    #[serde(default)]
+    pub example: Expected,
+    #[serde(default)]
+    pub input: Expected,
+}
+
+/// Answers for one input. Parts without an answer aren't checked.
+#[derive(Debug, Default, Deserialize)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Expected {
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+}
+
+impl Answers {
+    pub fn path(day: u8) -> PathBuf {
+        input::day_dir(day).join("answers.toml")
+    }
+
+    pub fn load(day: u8) -> Result<Self, String> {
+        let path = Answers::path(day);
+        let contents = fs::read_to_string(&path)
+            .map_err(|err| format!("Could not read {}: {err}", path.display()))?;
+        toml::from_str(&contents).map_err(|err| format!("Invalid {}: {err}", path.display()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    Example,
+    Input,
+    /// The nth example in the README
+    Readme(usize),
+}
+
+impl InputKind {
+    fn path(self, day: u8) -> PathBuf {
+        match self {
+            InputKind::Example => input::example_path(day),
+            InputKind::Input => input::default_path(day),
+            InputKind::Readme(_) => examples::readme_path(day),
+        }
+    }
+}
+
+impl Display for InputKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputKind::Example => write!(f, "example"),
+            InputKind::Input => write!(f, "input"),
+            InputKind::Readme(n) => write!(f, "readme {n}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Outcome {
+    Pass {
+        answer: String,
+        elapsed: Duration,
+    },
+    Fail {
+        expected: String,
+        actual: String,
+        elapsed: Duration,
+    },
+    /// The answers, input or parse failed before any part could run
+    Error(String),
+}
+
+/// The result of one part on one input, or of everything that could be checked if it failed early
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    pub kind: Option<InputKind>,
+    pub part: Option<Part>,
+    pub outcome: Outcome,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        matches!(self.outcome, Outcome::Pass { .. })
+    }
+}
+
+fn error(day: u8, kind: Option<InputKind>, reason: String) -> Check {
+    Check {
+        day,
+        kind,
+        part: None,
+        outcome: Outcome::Error(reason),
+    }
+}
+
+/// Runs the parts which have an expected answer on `contents`
+fn check(
+    day: u8,
+    kind: InputKind,
+    contents: &str,
+    expected: impl Fn(Part) -> Option<String>,
+    run: Runner,
+) -> Vec<Check> {
+    let parts: Vec<Part> = Part::BOTH
+        .into_iter()
+        .filter(|&part| expected(part).is_some())
+        .collect();
+    if parts.is_empty() {
+        return vec![];
+    }
+    let report = match run(contents, &parts, &Options::default()) {
+        Ok(report) => report,
+        Err(err) => return vec![error(day, Some(kind), format!("Invalid input, {err}"))],
+    };
+    report
+        .parts
+        .into_iter()
+        .map(|result| {
+            let expected = expected(result.part).unwrap_or_default();
+            let outcome = if result.answer == expected {
+                Outcome::Pass {
+                    answer: result.answer,
+                    elapsed: result.elapsed,
+                }
+            } else {
+                Outcome::Fail {
+                    expected,
+                    actual: result.answer,
+                    elapsed: result.elapsed,
+                }
+            };
+            Check {
+                day,
+                kind: Some(kind),
+                part: Some(result.part),
+                outcome,
+            }
+        })
+        .collect()
+}
+
+/// Runs each part that has a recorded answer, on the example and then the real input
+pub fn verify(day: u8, run: Runner) -> Vec<Check> {
+    let answers = match Answers::load(day) {
+        Ok(answers) => answers,
+        Err(reason) => return vec![error(day, None, reason)],
+    };
+
+    let mut checks = vec![];
+    for (kind, expected) in [
+        (InputKind::Example, &answers.example),
+        (InputKind::Input, &answers.input),
+    ] {
+        if Part::BOTH.iter().all(|&part| expected.get(part).is_none()) {
+            continue;
+        }
+        match InputSource::Path(kind.path(day)).read() {
+            Ok(contents) => checks.extend(check(
+                day,
+                kind,
+                &contents,
+                |part| expected.get(part).map(String::from),
+                run,
+            )),
+            Err(err) => checks.push(error(day, Some(kind), err.to_string())),
+        }
+    }
+    checks
+}
+
+/// Runs each part that has an answer stated alongside an example in the README
+pub fn verify_readme(day: u8, run: Runner) -> Vec<Check> {
+    let examples = match examples::readme(day) {
+        Ok(examples) if examples.is_empty() => {
+            return vec![error(day, None, "No examples found in README".to_string())]
+        }
+        Ok(examples) => examples,
+        Err(err) => return vec![error(day, None, err.to_string())],
+    };
+    examples
+        .iter()
+        .enumerate()
+        .flat_map(|(i, example)| {
+            check(
+                day,
+                InputKind::Readme(i + 1),
+                &example.input,
+                |part| example.answer(part).map(String::from),
+                run,
+            )
+        })
+        .collect()
+}
+
+/// Multi line answers are shortened to their first line
+fn summary(answer: &str) -> String {
+    match answer.split_once('\n') {
+        Some((first, _)) => format!("{first} ..."),
+        None => answer.to_string(),
+    }
+}
+
+/// A row per check, followed by the full answers of any failures
+pub struct Table<'a>(pub &'a [Check]);
+
+impl Display for Table<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>3}  {:<8}  {:>4}  {:<6}  {:>12}  answer",
+            "day", "input", "part", "result", "time"
+        )?;
+        for Check {
+            day,
+            kind,
+            part,
+            outcome,
+        } in self.0
+        {
+            let kind = kind.map_or("-".to_string(), |kind| kind.to_string());
+            let part = part.map_or("-".to_string(), |part| part.to_string());
+            let (result, elapsed, answer) = match outcome {
+                Outcome::Pass { answer, elapsed } => {
+                    ("pass", format!("{elapsed:.2?}"), summary(answer))
+                }
+                Outcome::Fail {
+                    actual, elapsed, ..
+                } => ("FAIL", format!("{elapsed:.2?}"), summary(actual)),
+                Outcome::Error(reason) => ("ERROR", "-".to_string(), reason.clone()),
+            };
+            writeln!(
+                f,
+                "{day:>3}  {kind:<8}  {part:>4}  {result:<6}  {elapsed:>12}  {answer}"
+            )?;
+        }
+
+        for check in self.0 {
+            if let Outcome::Fail {
+                expected, actual, ..
+            } = &check.outcome
+            {
+                let kind = check.kind.map_or("-".to_string(), |kind| kind.to_string());
+                let part = check.part.map_or("-".to_string(), |part| part.to_string());
+                writeln!(f, "\nDay {} {kind} part {part}", check.day)?;
+                writeln!(f, " expected:\n{expected}\n actual:\n{actual}")?;
+            }
+        }
+
+        let passed = self.0.iter().filter(|check| check.passed()).count();
+        writeln!(f, "\n{passed} passed, {} failed", self.0.len() - passed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::{self, DAYS};
+
+    use super::*;
+
+    #[test]
+    fn examples_match_answers() {
+        for day in DAYS {
+            let answers = Answers::load(day).unwrap();
+            assert!(answers.example.part1.is_some() && answers.example.part2.is_some());
+
+            let run = days::runner(day).unwrap();
+            let contents = InputSource::Path(input::example_path(day)).read().unwrap();
+            let report = run(&contents, &Part::BOTH, &Options::default()).unwrap();
+            for result in report.parts {
+                assert_eq!(
+                    Some(result.answer.as_str()),
+                    answers.example.get(result.part),
+                    "day {day}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn readme_examples_pass() {
+        for day in DAYS {
+            let checks = verify_readme(day, days::runner(day).unwrap());
+            assert!(!checks.is_empty(), "day {day}");
+            assert!(checks.iter().all(Check::passed), "{}", Table(&checks));
+        }
+    }
+}
@@ -0,0 +1,584 @@
+//! Animating a simulation in the terminal, redrawing a frame in place for each step
+
+use std::{
+    collections::VecDeque,
+    fmt::{self, Display},
+    io::{self, stdout, IsTerminal, Write},
+    time::{Duration, Instant},
+};
+
+pub use crossterm::style::Color;
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    queue,
+    style::{Print, ResetColor, SetForegroundColor},
+    terminal::{self, ClearType},
+};
+
+use crate::grid::{Grid2D, Pos};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    /// The terminal's own colour if None
+    pub colour: Option<Color>,
+}
+
+impl Cell {
+    pub fn plain(ch: char) -> Self {
+        Cell { ch, colour: None }
+    }
+}
+
+/// A picture of a simulation at one step
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub cells: Grid2D<Cell>,
+    /// Shown below the picture, e.g. the step number
+    pub caption: String,
+    /// Where the action is, kept in view when the frame is larger than the terminal
+    pub focus: Option<Pos>,
+}
+
+impl Frame {
+    /// A blank frame
+    pub fn new(height: usize, width: usize) -> Self {
+        Frame {
+            cells: Grid2D::filled(height, width, Cell::plain(' ')),
+            caption: String::new(),
+            focus: None,
+        }
+    }
+
+    /// A row for each line of `text`, padded with spaces to the longest
+    pub fn from_text(text: &str) -> Self {
+        let width = text.lines().map(|line| line.chars().count()).max();
+        let mut frame = Frame::new(text.lines().count(), width.unwrap_or(0));
+        for (i, line) in text.lines().enumerate() {
+            for (j, ch) in line.chars().enumerate() {
+                frame.set((i, j), ch);
+            }
+        }
+        frame
+    }
+
+    pub fn set(&mut self, pos: Pos, ch: char) {
+        self.cells[pos] = Cell::plain(ch);
+    }
+
+    pub fn paint(&mut self, pos: Pos, ch: char, colour: Color) {
+        self.cells[pos] = Cell {
+            ch,
+            colour: Some(colour),
+        };
+    }
+
+    /// The part of the frame that fits in `height` by `width`, centred on the focus as far as
+    /// the edges allow, otherwise the top left
+    pub fn crop(&self, height: usize, width: usize) -> Frame {
+        let (fi, fj) = self.focus.unwrap_or((0, 0));
+        let top = window_start(self.cells.height(), height, fi);
+        let left = window_start(self.cells.width(), width, fj);
+        let height = height.min(self.cells.height());
+        let width = width.min(self.cells.width());
+        let cells = (top..top + height)
+            .flat_map(|i| self.cells.row(i)[left..left + width].iter().copied())
+            .collect();
+        Frame {
+            cells: Grid2D::new(height, width, cells),
+            caption: self.caption.clone(),
+            focus: self.focus.map(|(i, j)| (i - top, j - left)),
+        }
+    }
+}
+
+/// First of `view` consecutive indices out of `len`, with `focus` as near the middle as possible
+fn window_start(len: usize, view: usize, focus: usize) -> usize {
+    if len <= view {
+        0
+    } else {
+        focus.saturating_sub(view / 2).min(len - view)
+    }
+}
+
+/// The picture without colours, then the caption
+impl Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.cells.rows() {
+            let line: String = row.iter().map(|cell| cell.ch).collect();
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        if !self.caption.is_empty() {
+            writeln!(f, "{}", self.caption)?;
+        }
+        Ok(())
+    }
+}
+
+/// A simulation that can be played a step at a time
+pub trait Animate {
+    /// Advances the simulation by one step, or returns false if it has already finished
+    fn step(&mut self) -> bool;
+    fn render(&self) -> Frame;
+
+    /// Steps taken so far, for simulations that can go back with `back`. The player then steps
+    /// back and goes to a step through the simulation itself, rather than through frames it has
+    /// recorded.
+    fn position(&self) -> Option<usize> {
+        None
+    }
+
+    /// Undoes the last step, or returns false if there's none to undo or the simulation can't
+    fn back(&mut self) -> bool {
+        false
+    }
+
+    /// Steps forwards or backwards until `n` steps have been taken, or as near as there are
+    fn jump(&mut self, n: usize) {
+        while self.position().is_some_and(|position| position > n) && self.back() {}
+        while self.position().is_some_and(|position| position < n) && self.step() {}
+    }
+}
+
+/// Plays an animation in the terminal, pausing at the end until quit.
+///
+/// Keys: space pauses, right or `.` steps forward, left or `,` steps back, a frame number then
+/// `g` or enter goes to that frame (or to that step, for simulations with a position), `+` and
+/// `-` change the number of steps per frame, and `q` or escape quit.
+///
+/// Stepping back replays recorded frames, as far back as the history goes, unless the simulation
+/// can step back itself.
+#[derive(Debug, Clone)]
+pub struct Player {
+    /// Most frames drawn per second
+    pub fps: u32,
+    /// Frames kept for stepping back through, when the simulation can't step back itself
+    pub history: usize,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Player {
+            fps: 30,
+            history: 1000,
+        }
+    }
+}
+
+impl Player {
+    /// Prints the last frame once done. When not writing to a terminal only the last frame is
+    /// printed, without animating.
+    pub fn play(&self, sim: &mut (impl Animate + ?Sized)) -> io::Result<()> {
+        let mut out = stdout();
+        if !out.is_terminal() {
+            while sim.step() {}
+            return write!(out, "{}", sim.render());
+        }
+
+        terminal::enable_raw_mode()?;
+        queue!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+        let result = self.run(sim, &mut out);
+        queue!(out, cursor::Show, terminal::LeaveAlternateScreen)?;
+        out.flush()?;
+        terminal::disable_raw_mode()?;
+        result?;
+        write!(out, "{}", sim.render())
+    }
+
+    fn run(&self, sim: &mut (impl Animate + ?Sized), out: &mut impl Write) -> io::Result<()> {
+        let mut view = view_size(terminal::size()?);
+        let mut playback = Playback::new(sim, view, self.history);
+        let frame_time = Duration::from_secs(1) / self.fps.max(1);
+        loop {
+            draw(out, &playback)?;
+            let next_frame = Instant::now() + frame_time;
+            // handle keys until it's time for the next frame
+            while event::poll(next_frame.saturating_duration_since(Instant::now()))? {
+                match event::read()? {
+                    Event::Key(key) if key.kind != KeyEventKind::Release => {
+                        if !playback.key(key, sim, view) {
+                            return Ok(());
+                        }
+                        draw(out, &playback)?;
+                    }
+                    // frames already recorded keep their size
+                    Event::Resize(columns, rows) => view = view_size((columns, rows)),
+                    _ => {}
+                }
+            }
+            if !playback.paused {
+                playback.forward(sim, view);
+            }
+        }
+    }
+}
+
+/// Plays with the default settings, reporting any terminal error
+pub fn play(sim: &mut (impl Animate + ?Sized)) {
+    if let Err(err) = Player::default().play(sim) {
+        eprintln!("Animation failed, {err}");
+    }
+}
+
+/// (height, width) for frames given the terminal's (columns, rows), leaving a line each for the
+/// caption and status
+fn view_size((columns, rows): (u16, u16)) -> (usize, usize) {
+    ((rows as usize).saturating_sub(2), columns as usize)
+}
+
+/// Recorded frames and where in them playback is. A simulation with a position is only ever
+/// recorded at its current step, as stepping back goes through it instead.
+struct Playback {
+    frames: VecDeque<Frame>,
+    /// Frames dropped from the front of the history
+    dropped: usize,
+    limit: usize,
+    /// Index of the frame on screen
+    shown: usize,
+    paused: bool,
+    /// Simulation steps per frame
+    speed: usize,
+    finished: bool,
+    /// Digits of a frame number being typed
+    typed: String,
+    /// The simulation's position at the frame on screen, if it has one
+    position: Option<usize>,
+}
+
+impl Playback {
+    fn new(sim: &(impl Animate + ?Sized), (height, width): (usize, usize), limit: usize) -> Self {
+        Playback {
+            frames: VecDeque::from([sim.render().crop(height, width)]),
+            dropped: 0,
+            limit: limit.max(1),
+            shown: 0,
+            paused: false,
+            speed: 1,
+            finished: false,
+            typed: String::new(),
+            position: sim.position(),
+        }
+    }
+
+    /// Records the simulation as it is, in place of every frame before it
+    fn rewound(&mut self, sim: &(impl Animate + ?Sized), (height, width): (usize, usize)) {
+        self.frames = VecDeque::from([sim.render().crop(height, width)]);
+        self.shown = 0;
+        self.position = sim.position();
+    }
+
+    fn is_live(&self) -> bool {
+        self.shown + 1 == self.frames.len()
+    }
+
+    /// Moves on a frame, running the simulation unless replaying after stepping back. Pauses at
+    /// the end of the simulation.
+    fn forward(&mut self, sim: &mut (impl Animate + ?Sized), (height, width): (usize, usize)) {
+        if !self.is_live() {
+            self.shown += 1;
+            return;
+        }
+        if self.finished {
+            self.paused = true;
+            return;
+        }
+        for _ in 0..self.speed {
+            if !sim.step() {
+                self.finished = true;
+                break;
+            }
+        }
+        self.frames.push_back(sim.render().crop(height, width));
+        self.position = sim.position();
+        let limit = if self.position.is_some() {
+            1
+        } else {
+            self.limit
+        };
+        while self.frames.len() > limit {
+            self.frames.pop_front();
+            self.dropped += 1;
+        }
+        self.shown = self.frames.len() - 1;
+    }
+
+    /// Moves back a frame, undoing its steps if the simulation can
+    fn back(&mut self, sim: &mut (impl Animate + ?Sized), view: (usize, usize)) {
+        if sim.position().is_none() {
+            self.shown = self.shown.saturating_sub(1);
+            return;
+        }
+        for _ in 0..self.speed {
+            if !sim.back() {
+                break;
+            }
+            self.finished = false;
+        }
+        self.rewound(sim, view);
+    }
+
+    /// Shows frame `target`, or step `target` of a simulation with a position, running the
+    /// simulation up to it if it hasn't been reached yet, or the earliest or latest there is.
+    /// Pauses there.
+    fn jump(&mut self, target: usize, sim: &mut (impl Animate + ?Sized), view: (usize, usize)) {
+        self.paused = true;
+        if sim.position().is_some() {
+            sim.jump(target);
+            // short of the target if the simulation ended first
+            self.finished = sim.position().is_some_and(|position| position < target);
+            self.rewound(sim, view);
+            return;
+        }
+        while self.dropped + self.frames.len() <= target && !self.finished {
+            self.shown = self.frames.len() - 1;
+            self.forward(sim, view);
+        }
+        self.shown = target
+            .saturating_sub(self.dropped)
+            .min(self.frames.len() - 1);
+    }
+
+    /// Returns false to quit
+    fn key(
+        &mut self,
+        key: KeyEvent,
+        sim: &mut (impl Animate + ?Sized),
+        view: (usize, usize),
+    ) -> bool {
+        let typed = std::mem::take(&mut self.typed);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Right | KeyCode::Char('.') => {
+                self.paused = true;
+                self.forward(sim, view);
+            }
+            KeyCode::Left | KeyCode::Char(',') => {
+                self.paused = true;
+                self.back(sim, view);
+            }
+            KeyCode::Char(digit) if digit.is_ascii_digit() => {
+                self.typed = typed + &digit.to_string()
+            }
+            KeyCode::Char('g') | KeyCode::Enter => {
+                if let Ok(target) = typed.parse() {
+                    self.jump(target, sim, view);
+                }
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => self.speed *= 2,
+            KeyCode::Char('-') => self.speed = (self.speed / 2).max(1),
+            _ => {}
+        }
+        true
+    }
+
+    fn status(&self) -> String {
+        let state = match (self.paused, self.finished && self.is_live()) {
+            (_, true) => "finished",
+            (true, false) => "paused",
+            (false, false) => "playing",
+        };
+        let (unit, at) = match self.position {
+            Some(position) => ("step", position),
+            None => ("frame", self.dropped + self.shown),
+        };
+        let go_to = if self.typed.is_empty() {
+            String::new()
+        } else {
+            format!("   go to {unit} {}_", self.typed)
+        };
+        format!(
+            "{unit} {at} ({state}, {} steps per frame)   space pause   \u{2190}/\u{2192} step   N g go to   +/- speed   q quit{go_to}",
+            self.speed
+        )
+    }
+}
+
+fn draw(out: &mut impl Write, playback: &Playback) -> io::Result<()> {
+    let frame = &playback.frames[playback.shown];
+    queue!(out, cursor::MoveTo(0, 0))?;
+    for row in frame.cells.rows() {
+        for cell in row {
+            match cell.colour {
+                Some(colour) => {
+                    queue!(out, SetForegroundColor(colour), Print(cell.ch), ResetColor)?
+                }
+                None => queue!(out, Print(cell.ch))?,
+            }
+        }
+        queue!(
+            out,
+            terminal::Clear(ClearType::UntilNewLine),
+            cursor::MoveToNextLine(1)
+        )?;
+    }
+    queue!(
+        out,
+        Print(&frame.caption),
+        terminal::Clear(ClearType::UntilNewLine),
+        cursor::MoveToNextLine(1),
+        Print(playback.status()),
+        terminal::Clear(ClearType::FromCursorDown)
+    )?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts up to `end`
+    struct Counter {
+        n: usize,
+        end: usize,
+    }
+
+    impl Animate for Counter {
+        fn step(&mut self) -> bool {
+            self.n += 1;
+            self.n <= self.end
+        }
+
+        fn render(&self) -> Frame {
+            Frame::from_text(&self.n.min(self.end).to_string())
+        }
+    }
+
+    /// Counts up to `end`, and can count back down
+    struct Reversible {
+        n: usize,
+        end: usize,
+    }
+
+    impl Animate for Reversible {
+        fn step(&mut self) -> bool {
+            if self.n == self.end {
+                return false;
+            }
+            self.n += 1;
+            true
+        }
+
+        fn render(&self) -> Frame {
+            Frame::from_text(&self.n.to_string())
+        }
+
+        fn position(&self) -> Option<usize> {
+            Some(self.n)
+        }
+
+        fn back(&mut self) -> bool {
+            if self.n == 0 {
+                return false;
+            }
+            self.n -= 1;
+            true
+        }
+    }
+
+    fn shown(playback: &Playback) -> String {
+        playback.frames[playback.shown].to_string()
+    }
+
+    #[test]
+    fn crop_follows_focus() {
+        let mut frame = Frame::from_text("abcde\nfghij\nklmno");
+        frame.focus = Some((1, 3));
+        assert_eq!(frame.crop(1, 3).to_string(), "hij\n");
+        assert_eq!(frame.crop(1, 3).focus, Some((0, 1)));
+        frame.focus = Some((0, 0));
+        assert_eq!(frame.crop(2, 2).to_string(), "ab\nfg\n");
+        assert_eq!(frame.crop(10, 10), frame);
+    }
+
+    #[test]
+    fn step_back_then_replay() {
+        let mut sim = Counter { n: 0, end: 3 };
+        let view = (10, 10);
+        let mut playback = Playback::new(&sim, view, 10);
+        playback.forward(&mut sim, view);
+        playback.forward(&mut sim, view);
+        assert_eq!(shown(&playback), "2\n");
+
+        playback.back(&mut sim, view);
+        playback.back(&mut sim, view);
+        playback.back(&mut sim, view);
+        assert_eq!(shown(&playback), "0\n");
+        playback.forward(&mut sim, view);
+        assert_eq!(shown(&playback), "1\n");
+        assert_eq!(sim.n, 2, "replaying shouldn't run the simulation");
+
+        for _ in 0..4 {
+            playback.forward(&mut sim, view);
+        }
+        assert_eq!(shown(&playback), "3\n");
+        assert!(playback.finished && playback.paused);
+    }
+
+    #[test]
+    fn jump_to_frame() {
+        let mut sim = Counter { n: 0, end: 5 };
+        let view = (10, 10);
+        let mut playback = Playback::new(&sim, view, 10);
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        for c in ['3', 'g'] {
+            playback.key(key(c), &mut sim, view);
+        }
+        assert_eq!(shown(&playback), "3\n");
+        assert!(playback.paused && playback.typed.is_empty());
+
+        playback.jump(1, &mut sim, view);
+        assert_eq!(shown(&playback), "1\n");
+        assert_eq!(sim.n, 3, "going back shouldn't run the simulation");
+        playback.jump(50, &mut sim, view);
+        assert_eq!(shown(&playback), "5\n");
+        assert!(playback.finished);
+    }
+
+    #[test]
+    fn history_is_limited() {
+        let mut sim = Counter { n: 0, end: 100 };
+        let view = (10, 10);
+        let mut playback = Playback::new(&sim, view, 3);
+        playback.speed = 2;
+        for _ in 0..5 {
+            playback.forward(&mut sim, view);
+        }
+        assert_eq!(playback.frames.len(), 3);
+        assert_eq!(shown(&playback), "10\n");
+        for _ in 0..5 {
+            playback.back(&mut sim, view);
+        }
+        assert_eq!(shown(&playback), "6\n");
+        assert!(playback.status().starts_with("frame 3 "));
+    }
+
+    #[test]
+    fn step_back_through_the_simulation() {
+        let mut sim = Reversible { n: 0, end: 20 };
+        let view = (10, 10);
+        let mut playback = Playback::new(&sim, view, 3);
+        playback.speed = 2;
+        for _ in 0..5 {
+            playback.forward(&mut sim, view);
+        }
+        assert_eq!(playback.frames.len(), 1);
+        // further back than a history of 3 frames would go
+        for _ in 0..4 {
+            playback.back(&mut sim, view);
+        }
+        assert_eq!((shown(&playback), sim.n), ("2\n".to_string(), 2));
+        assert!(playback.status().starts_with("step 2 "));
+
+        // counting steps rather than frames, whatever the speed
+        playback.jump(15, &mut sim, view);
+        assert_eq!(shown(&playback), "15\n");
+        playback.jump(50, &mut sim, view);
+        assert_eq!(sim.n, 20);
+        assert!(playback.finished && playback.paused);
+        playback.jump(0, &mut sim, view);
+        assert_eq!(shown(&playback), "0\n");
+    }
+}
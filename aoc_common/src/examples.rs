@@ -0,0 +1,290 @@
+//! Worked examples and their answers, pulled out of each day's README to run as test cases
+
+use std::path::PathBuf;
+
+use crate::{
+    input::{self, InputError, InputSource},
+    solution::{Part, Solution},
+};
+
+/// A worked example from a day's README, with the answers it states for each part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Example {
+    fn new(input: String) -> Self {
+        Example {
+            input,
+            part1: None,
+            part2: None,
+        }
+    }
+
+    pub fn answer(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+
+    fn answer_mut(&mut self, part: Part) -> &mut Option<String> {
+        match part {
+            Part::One => &mut self.part1,
+            Part::Two => &mut self.part2,
+        }
+    }
+}
+
+pub fn readme_path(day: u8) -> PathBuf {
+    input::day_dir(day).join("README.md")
+}
+
+/// The examples in `day N/README.md`
+pub fn readme(day: u8) -> Result<Vec<Example>, InputError> {
+    InputSource::Path(readme_path(day))
+        .read()
+        .map(|contents| extract(&contents))
+}
+
+/// Values emphasised as answers: `_7_` in older READMEs, *`21`* or _`93`_ in newer ones
+fn stated_answers(text: &str) -> Vec<&str> {
+    // code spans are every other piece between backticks
+    let pieces: Vec<&str> = text.split('`').collect();
+    let mut answers = vec![];
+    for k in (1..pieces.len().saturating_sub(1)).step_by(2) {
+        let (before, span, after) = (pieces[k - 1], pieces[k], pieces[k + 1]);
+        if span.len() > 2 && span.starts_with('_') && span.ends_with('_') {
+            answers.push(&span[1..span.len() - 1]);
+        } else if (before.ends_with('*') && after.starts_with('*'))
+            || (before.ends_with('_') && after.starts_with('_'))
+        {
+            answers.push(span);
+        }
+    }
+    answers
+}
+
+/// A list item giving an example inline, e.g. "`bvwbjplbgvbhsrlpgdmjqwftvncz`: first marker
+/// after character `_5_`"
+fn inline_example(item: &str) -> Option<(&str, &str)> {
+    let (input, rest) = item.strip_prefix('`')?.split_once('`')?;
+    rest.starts_with(':')
+        .then(|| stated_answers(rest).last().copied())
+        .flatten()
+        .map(|answer| (input, answer))
+}
+
+/// Whether the last sentence of a paragraph introduces the example, as in "For example:" or
+/// "Here's a larger example:", rather than referring back to one
+fn introduces_example(paragraph: &str) -> bool {
+    let last = match paragraph
+        .rfind(['.', '!', '?'])
+        .filter(|&i| i + 1 < paragraph.len())
+    {
+        Some(i) => paragraph[i + 1..].trim_start(),
+        None => paragraph,
+    };
+    last.starts_with("For example") || last.ends_with("example:")
+}
+
+/// For parts which don't state an answer in words
+fn drawn_answer(example: &mut Example, part: Part, block: Option<String>) {
+    let answer = example.answer_mut(part);
+    if answer.is_none() {
+        *answer = block;
+    }
+}
+
+fn is_code(line: &str) -> bool {
+    line.starts_with("    ")
+}
+
+/// Pulls the examples out of a pandoc converted puzzle page.
+///
+/// Examples are the code blocks introduced as one, and the list items giving an input and its
+/// answer. A part's answer is the last value emphasised in its section, and belongs to the
+/// example most recently given. Where none is stated (day 10 draws its answer), it's the
+/// section's last code block instead.
+pub fn extract(readme: &str) -> Vec<Example> {
+    let lines: Vec<&str> = readme.lines().collect();
+    let mut examples: Vec<Example> = vec![];
+    let mut current: Option<usize> = None;
+    let mut part = Part::One;
+    // whether the rest of the section is past the puzzle's own answer
+    let mut answered = false;
+    let mut stated = false;
+    let mut last_block: Option<String> = None;
+    let mut paragraph = String::new();
+    let mut previous_paragraph = String::new();
+
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if line.contains("-- Part Two") {
+            if let (Some(j), false) = (current, stated) {
+                drawn_answer(&mut examples[j], part, last_block.take());
+            }
+            (part, answered, stated) = (Part::Two, false, false);
+        } else if line.starts_with("Your puzzle answer was") {
+            answered = true;
+        } else if is_code(line) && i > 0 && lines[i - 1].is_empty() {
+            let start = i;
+            while i < lines.len()
+                && (is_code(lines[i])
+                    || (lines[i].is_empty() && lines.get(i + 1).is_some_and(|l| is_code(l))))
+            {
+                i += 1;
+            }
+            let block: Vec<&str> = lines[start..i]
+                .iter()
+                .map(|l| l.get(4..).unwrap_or(""))
+                .collect();
+            let block = block.join("\n");
+            if introduces_example(&previous_paragraph) && !answered {
+                examples.push(Example::new(block));
+                current = Some(examples.len() - 1);
+                last_block = None;
+            } else if !answered {
+                last_block = Some(block);
+            }
+            previous_paragraph.clear();
+            continue;
+        } else if line.is_empty() {
+            if !paragraph.is_empty() {
+                previous_paragraph = std::mem::take(&mut paragraph);
+            }
+        } else if !answered {
+            // list items continue onto indented lines
+            let mut item = line.to_string();
+            let is_item = line.starts_with("*   ") || line.starts_with("-   ");
+            while is_item && lines.get(i + 1).is_some_and(|l| is_code(l)) {
+                i += 1;
+                item = format!("{item} {}", lines[i].trim_start());
+            }
+            if let Some((input, answer)) =
+                item.get(4..).filter(|_| is_item).and_then(inline_example)
+            {
+                let j = match examples.iter().position(|e| e.input == input) {
+                    Some(j) => j,
+                    None => {
+                        examples.push(Example::new(input.to_string()));
+                        examples.len() - 1
+                    }
+                };
+                *examples[j].answer_mut(part) = Some(answer.to_string());
+            } else if let (Some(j), Some(answer)) = (current, stated_answers(&item).last()) {
+                *examples[j].answer_mut(part) = Some(answer.to_string());
+                stated = true;
+            }
+            if !paragraph.is_empty() {
+                paragraph.push(' ');
+            }
+            paragraph += &item;
+        }
+        i += 1;
+    }
+    if let (Some(j), false) = (current, stated) {
+        drawn_answer(&mut examples[j], part, last_block);
+    }
+    examples
+}
+
+/// Runs each of a day's README examples through its solution, panicking on a wrong answer
+pub fn check<S: Solution>() {
+    let examples = readme(S::DAY).unwrap_or_else(|err| panic!("{err}"));
+    assert!(
+        !examples.is_empty(),
+        "No examples in day {}'s README",
+        S::DAY
+    );
+    for (i, example) in examples.iter().enumerate() {
+        let parsed = S::parse(&example.input)
+            .unwrap_or_else(|err| panic!("Invalid example {}, {err}", i + 1));
+        for part in Part::BOTH {
+            if let Some(expected) = example.answer(part) {
+                let actual = match part {
+                    Part::One => S::part1(&parsed),
+                    Part::Two => S::part2(&parsed),
+                };
+                assert_eq!(actual, expected, "example {} part {part}", i + 1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_in_either_style() {
+        assert_eq!(
+            stated_answers("after character `_5_`, not `5`, or *`21`* and _`93`_"),
+            ["5", "21", "93"]
+        );
+    }
+
+    #[test]
+    fn examples_and_answers() {
+        let readme = "\
+Consider this:
+
+    not an example
+
+For example, suppose:
+
+    1
+    2
+
+    3
+
+There are `_6_` in total.
+
+-   `abc`: three is *`3`*
+
+\\--- Part Two ---
+-----------------
+
+-   `abc`: still `_3_`
+
+Now there are *`7`*.
+
+Your puzzle answer was `12`, not `_12_`.
+";
+        assert_eq!(
+            extract(readme),
+            [
+                Example {
+                    input: "1\n2\n\n3".to_string(),
+                    part1: Some("6".to_string()),
+                    part2: Some("7".to_string()),
+                },
+                Example {
+                    input: "abc".to_string(),
+                    part1: Some("3".to_string()),
+                    part2: Some("3".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn drawn_answer() {
+        let readme = "\
+For example:
+
+    input
+
+## \\-\\-- Part Two \\-\\-- {#part2}
+
+The example draws:
+
+    #..#
+";
+        assert_eq!(extract(readme)[0].part2.as_deref(), Some("#..#"));
+    }
+}
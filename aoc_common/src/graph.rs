@@ -0,0 +1,335 @@
+//! Shortest paths, on graphs stored as adjacency maps or given by a neighbour function
+
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    marker::PhantomData,
+};
+
+pub type Cost = u32;
+
+pub trait Graph {
+    type Node: Copy + Eq + Hash;
+
+    /// Nodes one step from `node`, with the cost of each step
+    fn edges(&self, node: Self::Node) -> impl Iterator<Item = (Self::Node, Cost)>;
+}
+
+/// Each node's outgoing edges
+#[derive(Debug, Clone)]
+pub struct AdjacencyMap<K> {
+    pub edges: HashMap<K, Vec<(K, Cost)>>,
+}
+
+impl<K: Copy + Eq + Hash> AdjacencyMap<K> {
+    pub fn new() -> Self {
+        AdjacencyMap {
+            edges: HashMap::new(),
+        }
+    }
+
+    pub fn add_edge(&mut self, from: K, to: K, cost: Cost) {
+        self.edges.entry(from).or_default().push((to, cost));
+        self.edges.entry(to).or_default();
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = K> + '_ {
+        self.edges.keys().copied()
+    }
+}
+
+impl<K: Copy + Eq + Hash> Default for AdjacencyMap<K> {
+    fn default() -> Self {
+        AdjacencyMap::new()
+    }
+}
+
+/// Every edge costs 1
+impl<K: Copy + Eq + Hash> From<HashMap<K, Vec<K>>> for AdjacencyMap<K> {
+    fn from(neighbours: HashMap<K, Vec<K>>) -> Self {
+        let mut map = AdjacencyMap::new();
+        for (from, tos) in neighbours {
+            map.edges.entry(from).or_default();
+            for to in tos {
+                map.add_edge(from, to, 1);
+            }
+        }
+        map
+    }
+}
+
+impl<K: Copy + Eq + Hash> Graph for AdjacencyMap<K> {
+    type Node = K;
+
+    fn edges(&self, node: K) -> impl Iterator<Item = (K, Cost)> {
+        self.edges.get(&node).into_iter().flatten().copied()
+    }
+}
+
+/// A graph whose edges are found by calling a function, e.g. the moves allowed from a space
+/// in a grid
+pub struct Implicit<K, F> {
+    neighbours: F,
+    node: PhantomData<fn(K)>,
+}
+
+pub fn implicit<K, F, I>(neighbours: F) -> Implicit<K, F>
+where
+    F: Fn(K) -> I,
+    I: IntoIterator<Item = (K, Cost)>,
+{
+    Implicit {
+        neighbours,
+        node: PhantomData,
+    }
+}
+
+impl<K, F, I> Graph for Implicit<K, F>
+where
+    K: Copy + Eq + Hash,
+    F: Fn(K) -> I,
+    I: IntoIterator<Item = (K, Cost)>,
+{
+    type Node = K;
+
+    fn edges(&self, node: K) -> impl Iterator<Item = (K, Cost)> {
+        (self.neighbours)(node).into_iter()
+    }
+}
+
+/// The result of a search: how far each reached node is from the nearest start, and how it was
+/// reached
+#[derive(Debug, Clone)]
+pub struct Paths<K> {
+    pub dist: HashMap<K, Cost>,
+    previous: HashMap<K, K>,
+}
+
+impl<K: Copy + Eq + Hash> Paths<K> {
+    fn new() -> Self {
+        Paths {
+            dist: HashMap::new(),
+            previous: HashMap::new(),
+        }
+    }
+
+    pub fn distance(&self, node: K) -> Option<Cost> {
+        self.dist.get(&node).copied()
+    }
+
+    /// The node before `node` on its shortest path, None for starts and unreached nodes
+    pub fn previous(&self, node: K) -> Option<K> {
+        self.previous.get(&node).copied()
+    }
+
+    /// Nodes from a start to `node` inclusive, if it was reached
+    pub fn path_to(&self, node: K) -> Option<Vec<K>> {
+        self.dist.get(&node)?;
+        let mut path = vec![node];
+        while let Some(&previous) = self.previous.get(path.last().unwrap()) {
+            path.push(previous);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Fewest steps to every node reachable from any of `starts`, ignoring the cost of edges
+pub fn bfs<G: Graph>(graph: &G, starts: impl IntoIterator<Item = G::Node>) -> Paths<G::Node> {
+    let mut paths = Paths::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if paths.dist.insert(start, 0).is_none() {
+            queue.push_back(start);
+        }
+    }
+    while let Some(node) = queue.pop_front() {
+        let dist = paths.dist[&node];
+        for (next, _) in graph.edges(node) {
+            if let Entry::Vacant(entry) = paths.dist.entry(next) {
+                entry.insert(dist + 1);
+                paths.previous.insert(next, node);
+                queue.push_back(next);
+            }
+        }
+    }
+    paths
+}
+
+/// Lowest total cost to every node reachable from any of `starts`
+pub fn dijkstra<G: Graph>(graph: &G, starts: impl IntoIterator<Item = G::Node>) -> Paths<G::Node> {
+    search(graph, starts, None, |_| 0)
+}
+
+/// Lowest cost path from `start` to `goal`, and its cost. `heuristic` estimates the remaining
+/// cost from a node, and must never overestimate it for the path found to be the cheapest.
+pub fn astar<G, H>(
+    graph: &G,
+    start: G::Node,
+    goal: G::Node,
+    heuristic: H,
+) -> Option<(Cost, Vec<G::Node>)>
+where
+    G: Graph,
+    H: Fn(G::Node) -> Cost,
+{
+    let paths = search(graph, [start], Some(goal), heuristic);
+    Some((paths.distance(goal)?, paths.path_to(goal)?))
+}
+
+/// Shortest paths from each of `nodes` to every node
+pub fn all_pairs<G: Graph>(
+    graph: &G,
+    nodes: impl IntoIterator<Item = G::Node>,
+) -> HashMap<G::Node, Paths<G::Node>> {
+    nodes
+        .into_iter()
+        .map(|node| (node, dijkstra(graph, [node])))
+        .collect()
+}
+
+/// A node waiting to be expanded, ordered so the heap pops the lowest estimate first
+struct Queued<K> {
+    estimate: Cost,
+    dist: Cost,
+    node: K,
+}
+
+impl<K> PartialEq for Queued<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl<K> Eq for Queued<K> {}
+
+impl<K> PartialOrd for Queued<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K> Ord for Queued<K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate)
+    }
+}
+
+/// A*, stopping early once `goal` is expanded. Dijkstra's algorithm when the heuristic is 0.
+fn search<G, H>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    goal: Option<G::Node>,
+    heuristic: H,
+) -> Paths<G::Node>
+where
+    G: Graph,
+    H: Fn(G::Node) -> Cost,
+{
+    let mut paths = Paths::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        paths.dist.insert(start, 0);
+        heap.push(Queued {
+            estimate: heuristic(start),
+            dist: 0,
+            node: start,
+        });
+    }
+    while let Some(Queued { dist, node, .. }) = heap.pop() {
+        if dist > paths.dist[&node] {
+            // already expanded by a cheaper route
+            continue;
+        }
+        if Some(node) == goal {
+            break;
+        }
+        for (next, cost) in graph.edges(node) {
+            let next_dist = dist + cost;
+            if paths.dist.get(&next).is_none_or(|&d| next_dist < d) {
+                paths.dist.insert(next, next_dist);
+                paths.previous.insert(next, node);
+                heap.push(Queued {
+                    estimate: next_dist + heuristic(next),
+                    dist: next_dist,
+                    node: next,
+                });
+            }
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a -1- b -1- c, with a shortcut a -5- c and d unreachable
+    fn weighted() -> AdjacencyMap<char> {
+        let mut graph = AdjacencyMap::new();
+        for (from, to, cost) in [('a', 'b', 1), ('b', 'c', 1), ('a', 'c', 5)] {
+            graph.add_edge(from, to, cost);
+            graph.add_edge(to, from, cost);
+        }
+        graph.edges.insert('d', vec![]);
+        graph
+    }
+
+    #[test]
+    fn bfs_counts_steps_and_dijkstra_costs() {
+        let graph = weighted();
+        let steps = bfs(&graph, ['a']);
+        assert_eq!(steps.distance('c'), Some(1));
+        assert_eq!(steps.path_to('c'), Some(vec!['a', 'c']));
+        assert_eq!(steps.path_to('d'), None);
+
+        let costs = dijkstra(&graph, ['a']);
+        assert_eq!(costs.distance('c'), Some(2));
+        assert_eq!(costs.path_to('c'), Some(vec!['a', 'b', 'c']));
+        assert_eq!(costs.distance('d'), None);
+    }
+
+    #[test]
+    fn multi_source_from_nearest_start() {
+        let graph = AdjacencyMap::from(HashMap::from([
+            (1, vec![2]),
+            (2, vec![3]),
+            (3, vec![4]),
+            (4, vec![]),
+        ]));
+        let paths = bfs(&graph, [1, 3]);
+        assert_eq!(paths.distance(4), Some(1));
+        assert_eq!(paths.path_to(4), Some(vec![3, 4]));
+        assert_eq!(paths.previous(3), None);
+    }
+
+    #[test]
+    fn astar_on_implicit_grid() {
+        // 10x10 open grid with a wall down column 5, open only at row 9
+        let open =
+            |(x, y): (i32, i32)| (0..10).contains(&x) && (0..10).contains(&y) && (x != 5 || y == 9);
+        let grid = implicit(|(x, y): (i32, i32)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(move |&p| open(p))
+                .map(|p| (p, 1))
+        });
+        let goal = (9, 0);
+        let manhattan = |(x, y): (i32, i32)| x.abs_diff(goal.0) + y.abs_diff(goal.1);
+        let (cost, path) = astar(&grid, (0, 0), goal, manhattan).unwrap();
+        assert_eq!(cost, 27);
+        assert_eq!(path.len(), 28);
+        assert_eq!(dijkstra(&grid, [(0, 0)]).distance(goal), Some(27));
+        assert_eq!(astar(&grid, (0, 0), (5, 0), manhattan), None);
+    }
+
+    #[test]
+    fn all_pairs_distances() {
+        let graph = weighted();
+        let paths = all_pairs(&graph, graph.nodes());
+        assert_eq!(paths[&'c'].distance('a'), Some(2));
+        assert_eq!(paths[&'d'].distance('d'), Some(0));
+        assert_eq!(paths[&'d'].distance('a'), None);
+    }
+}
@@ -0,0 +1,239 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::parse::ParseError;
+
+/// (row, column)
+pub type Pos = (usize, usize);
+
+/// A move of (rows, columns)
+pub type Step = (isize, isize);
+
+pub const UP: Step = (-1, 0);
+pub const DOWN: Step = (1, 0);
+pub const LEFT: Step = (0, -1);
+pub const RIGHT: Step = (0, 1);
+
+/// Steps to the 4 orthogonally adjacent positions
+pub const ORTHOGONAL: [Step; 4] = [UP, RIGHT, DOWN, LEFT];
+
+/// Steps to all 8 adjacent positions, including diagonals
+pub const ADJACENT: [Step; 8] = [UP, (-1, 1), RIGHT, (1, 1), DOWN, (1, -1), LEFT, (-1, -1)];
+
+/// Dense grid stored row by row in a single `Vec`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid2D<T> {
+    cells: Vec<T>,
+    height: usize,
+    width: usize,
+}
+
+impl<T> Grid2D<T> {
+    /// Panics unless `cells` holds exactly `height * width` values
+    pub fn new(height: usize, width: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            height * width,
+            "grid cells don't fill the grid"
+        );
+        Grid2D {
+            cells,
+            height,
+            width,
+        }
+    }
+
+    /// Converts each char of each line with `f`, which is also given the char's position.
+    /// Fails at the first char `f` rejects, or at a line of a different width to the first.
+    pub fn parse<F, E>(s: &str, mut f: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(Pos, char) -> Result<T, E>,
+        E: Display,
+    {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for (i, line) in s.lines().enumerate() {
+            let before = cells.len();
+            for (j, (k, c)) in line.char_indices().enumerate() {
+                let cell = f((i, j), c)
+                    .map_err(|err| ParseError::new(s, &line[k..k + c.len_utf8()], err))?;
+                cells.push(cell);
+            }
+            let row_width = cells.len() - before;
+            match width {
+                Some(width) if row_width != width => {
+                    let reason = format!("expected {width} columns, found {row_width}");
+                    return Err(ParseError::new(s, line, reason));
+                }
+                _ => width = Some(row_width),
+            }
+            height += 1;
+        }
+        Ok(Grid2D::new(height, width.unwrap_or(0), cells))
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn contains(&self, (i, j): Pos) -> bool {
+        i < self.height && j < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos).then(|| &self[pos])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        match self.contains(pos) {
+            true => Some(&mut self[pos]),
+            false => None,
+        }
+    }
+
+    /// All positions, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |i| (0..width).map(move |j| (i, j)))
+    }
+
+    /// Each position with its value, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.cells[i * self.width..(i + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks of an empty width would never end
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, j: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            j < self.width,
+            "column {j} outside a grid {} wide",
+            self.width
+        );
+        self.cells.iter().skip(j).step_by(self.width)
+    }
+
+    /// The position one `step` from `pos`, if it's inside the grid
+    pub fn step(&self, (i, j): Pos, (di, dj): Step) -> Option<Pos> {
+        let next = (i.checked_add_signed(di)?, j.checked_add_signed(dj)?);
+        self.contains(next).then_some(next)
+    }
+
+    /// Orthogonally adjacent positions inside the grid
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .filter_map(move |step| self.step(pos, step))
+    }
+
+    /// Adjacent positions inside the grid, including diagonals
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ADJACENT
+            .into_iter()
+            .filter_map(move |step| self.step(pos, step))
+    }
+
+    /// Positions from the one after `pos` repeatedly moving by `step`, up to the edge
+    pub fn ray(&self, pos: Pos, step: Step) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.step(pos, step), move |&p| self.step(p, step))
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid2D<U> {
+        Grid2D::new(self.height, self.width, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T: Clone> Grid2D<T> {
+    pub fn filled(height: usize, width: usize, value: T) -> Self {
+        Grid2D::new(height, width, vec![value; height * width])
+    }
+}
+
+impl<T> Index<Pos> for Grid2D<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): Pos) -> &Self::Output {
+        assert!(
+            i < self.height && j < self.width,
+            "({i}, {j}) outside a {}x{} grid",
+            self.height,
+            self.width
+        );
+        &self.cells[i * self.width + j]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid2D<T> {
+    fn index_mut(&mut self, (i, j): Pos) -> &mut Self::Output {
+        assert!(
+            i < self.height && j < self.width,
+            "({i}, {j}) outside a {}x{} grid",
+            self.height,
+            self.width
+        );
+        &mut self.cells[i * self.width + j]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits() -> Grid2D<u32> {
+        Grid2D::parse("123\n456", |_, c| c.to_digit(10).ok_or("not a digit")).unwrap()
+    }
+
+    #[test]
+    fn parse_rows_and_columns() {
+        let grid = digits();
+        assert_eq!((grid.height(), grid.width()), (2, 3));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), [3, 6]);
+        assert_eq!(grid.rows().count(), 2);
+
+        let err = Grid2D::parse("12\n3", |_, c| c.to_digit(10).ok_or("not a digit")).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        let err = Grid2D::parse("12\n3x", |_, c| c.to_digit(10).ok_or("not a digit")).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid = digits();
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbours8((0, 1)).collect::<Vec<_>>(),
+            [(0, 2), (1, 2), (1, 1), (1, 0), (0, 0)]
+        );
+        assert_eq!(grid.get((2, 0)), None);
+    }
+
+    #[test]
+    fn rays_end_at_the_edge() {
+        let grid = digits();
+        assert_eq!(
+            grid.ray((0, 0), RIGHT).collect::<Vec<_>>(),
+            [(0, 1), (0, 2)]
+        );
+        assert_eq!(grid.ray((1, 2), (-1, -1)).collect::<Vec<_>>(), [(0, 1)]);
+        assert_eq!(grid.ray((0, 0), UP).count(), 0);
+    }
+}
@@ -0,0 +1,559 @@
+//! Writing an animation as an image: a PNG or PPM of one frame, or an animated GIF of the run
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Display},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use crate::{
+    animation::{Animate, Cell, Color, Frame},
+    parse::ParseError,
+};
+
+pub type Rgb = [u8; 3];
+
+/// The most cells, over all its frames, a GIF is drawn from. A byte each is kept until the end.
+pub const MAX_GIF_CELLS: usize = 1 << 28;
+
+/// The colour of each cell in an image
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    /// For blank cells, plain spaces and dots
+    pub background: Rgb,
+    /// For any other cell without a colour
+    pub foreground: Rgb,
+    /// Colours for particular characters, in place of the frame's own
+    pub chars: HashMap<char, Rgb>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette {
+            background: [0, 0, 0],
+            foreground: [204, 204, 204],
+            chars: HashMap::new(),
+        }
+    }
+}
+
+impl Palette {
+    pub fn colour(&self, cell: Cell) -> Rgb {
+        if let Some(&rgb) = self.chars.get(&cell.ch) {
+            return rgb;
+        }
+        match (cell.colour.and_then(rgb), cell.ch) {
+            (Some(rgb), _) => rgb,
+            (None, ' ' | '.') => self.background,
+            (None, _) => self.foreground,
+        }
+    }
+}
+
+/// Comma separated `key=rrggbb` pairs, where the key is `bg`, `fg` or a single character, e.g.
+/// `bg=ffffff,fg=000000,#=808080,o=ffd700`. Anything not given keeps its default.
+impl FromStr for Palette {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut palette = Palette::default();
+        for entry in s
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let (key, value) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("Expected key=rrggbb, got {entry}"))?;
+            let rgb = hex(value)?;
+            let mut chars = key.chars();
+            match (key, chars.next(), chars.next()) {
+                ("bg" | "background", _, _) => palette.background = rgb,
+                ("fg" | "foreground", _, _) => palette.foreground = rgb,
+                (_, Some(ch), None) => {
+                    palette.chars.insert(ch, rgb);
+                }
+                _ => return Err(format!("Expected bg, fg or a single character, got {key}")),
+            }
+        }
+        Ok(palette)
+    }
+}
+
+/// `rrggbb`, optionally after a `#`
+fn hex(s: &str) -> Result<Rgb, String> {
+    let digits = s.strip_prefix('#').unwrap_or(s);
+    let channel = |i: usize| {
+        digits
+            .get(i..i + 2)
+            .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+    };
+    match (digits.len(), channel(0), channel(2), channel(4)) {
+        (6, Some(r), Some(g), Some(b)) => Ok([r, g, b]),
+        _ => Err(format!("Expected a colour as rrggbb, got {s}")),
+    }
+}
+
+/// A terminal colour as RGB, using the usual xterm values. None for the terminal's own colour.
+pub fn rgb(colour: Color) -> Option<Rgb> {
+    Some(match colour {
+        Color::Reset => return None,
+        Color::Black => [0, 0, 0],
+        Color::DarkRed => [128, 0, 0],
+        Color::DarkGreen => [0, 128, 0],
+        Color::DarkYellow => [128, 128, 0],
+        Color::DarkBlue => [0, 0, 128],
+        Color::DarkMagenta => [128, 0, 128],
+        Color::DarkCyan => [0, 128, 128],
+        Color::Grey => [192, 192, 192],
+        Color::DarkGrey => [128, 128, 128],
+        Color::Red => [255, 0, 0],
+        Color::Green => [0, 255, 0],
+        Color::Yellow => [255, 255, 0],
+        Color::Blue => [0, 0, 255],
+        Color::Magenta => [255, 0, 255],
+        Color::Cyan => [0, 255, 255],
+        Color::White => [255, 255, 255],
+        Color::Rgb { r, g, b } => [r, g, b],
+        Color::AnsiValue(n) => ansi(n),
+    })
+}
+
+fn ansi(n: u8) -> Rgb {
+    const NAMED: [Color; 16] = [
+        Color::Black,
+        Color::DarkRed,
+        Color::DarkGreen,
+        Color::DarkYellow,
+        Color::DarkBlue,
+        Color::DarkMagenta,
+        Color::DarkCyan,
+        Color::Grey,
+        Color::DarkGrey,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::White,
+    ];
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    match n {
+        0..=15 => rgb(NAMED[n as usize]).unwrap(),
+        // 6x6x6 colour cube
+        16..=231 => {
+            let n = (n - 16) as usize;
+            [LEVELS[n / 36], LEVELS[n / 6 % 6], LEVELS[n % 6]]
+        }
+        // greys
+        _ => [8 + 10 * (n - 232); 3],
+    }
+}
+
+/// Pixels of a frame, `scale` to a side for each cell
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub height: usize,
+    pub width: usize,
+    /// Row by row
+    pub pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(frame: &Frame, palette: &Palette, scale: usize) -> Self {
+        let (height, width) = (frame.cells.height(), frame.cells.width());
+        let colours = colours(frame, palette, height, width);
+        Image {
+            height: height * scale,
+            width: width * scale,
+            pixels: scaled(&colours, width, scale),
+        }
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        self.pixels.iter().flatten().copied().collect()
+    }
+
+    /// Binary PPM (P6)
+    pub fn write_ppm(&self, out: &mut dyn Write) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.bytes())
+    }
+
+    pub fn write_png(&self, out: &mut dyn Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.bytes())
+            .map_err(io::Error::other)
+    }
+}
+
+/// The colour of each cell, padded with the background to `height` by `width` cells
+fn colours(frame: &Frame, palette: &Palette, height: usize, width: usize) -> Vec<Rgb> {
+    let mut colours = vec![palette.background; height * width];
+    for ((i, j), &cell) in frame.cells.iter() {
+        colours[i * width + j] = palette.colour(cell);
+    }
+    colours
+}
+
+/// Each of `cells`, in rows of `width`, repeated into a `scale` by `scale` block
+fn scaled<T: Copy>(cells: &[T], width: usize, scale: usize) -> Vec<T> {
+    let mut pixels = Vec::with_capacity(cells.len() * scale * scale);
+    for row in cells.chunks(width.max(1)) {
+        for _ in 0..scale {
+            for &cell in row {
+                pixels.extend(std::iter::repeat_n(cell, scale));
+            }
+        }
+    }
+    pixels
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Png,
+    Ppm,
+    Gif,
+}
+
+impl Format {
+    /// Guessed from a file's extension
+    pub fn from_path(path: &Path) -> Option<Format> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "png" => Ok(Format::Png),
+            "ppm" => Ok(Format::Ppm),
+            "gif" => Ok(Format::Gif),
+            _ => Err(format!("Expected png, ppm or gif, got {s}")),
+        }
+    }
+}
+
+/// How to write an animation as an image. Stills show its state after `steps`, or at the end.
+#[derive(Debug, Clone)]
+pub struct Export {
+    pub format: Format,
+    pub palette: Palette,
+    /// Pixels along each side of a cell
+    pub scale: usize,
+    /// Steps to run before stopping, otherwise to the end
+    pub steps: Option<usize>,
+    /// Steps between GIF frames
+    pub every: usize,
+    /// Hundredths of a second each GIF frame is shown for
+    pub delay: u16,
+}
+
+impl Default for Export {
+    fn default() -> Self {
+        Export {
+            format: Format::Png,
+            palette: Palette::default(),
+            scale: 4,
+            steps: None,
+            every: 1,
+            delay: 5,
+        }
+    }
+}
+
+impl Export {
+    /// Runs `sim` once. A GIF's frames are kept as a byte a cell until the end, when its size and
+    /// colours are known, and it gives up past [`MAX_GIF_CELLS`] cells over all of them.
+    pub fn write(&self, sim: &mut dyn Animate, out: &mut dyn Write) -> io::Result<()> {
+        let scale = self.scale.max(1);
+        match self.format {
+            Format::Png | Format::Ppm => {
+                let mut taken = 0;
+                while self.steps.is_none_or(|steps| taken < steps) && sim.step() {
+                    taken += 1;
+                }
+                let image = Image::new(&sim.render(), &self.palette, scale);
+                match self.format {
+                    Format::Png => image.write_png(out),
+                    _ => image.write_ppm(out),
+                }
+            }
+            Format::Gif => self.write_gif(sim, scale, MAX_GIF_CELLS, out),
+        }
+    }
+
+    /// Runs `sim`, passing `f` the first frame, one every `every` steps, and the last
+    fn frames(
+        &self,
+        sim: &mut dyn Animate,
+        mut f: impl FnMut(Frame) -> io::Result<()>,
+    ) -> io::Result<()> {
+        f(sim.render())?;
+        let (mut taken, mut unseen) = (0, 0);
+        while self.steps.is_none_or(|steps| taken < steps) && sim.step() {
+            taken += 1;
+            unseen += 1;
+            if unseen == self.every.max(1) {
+                f(sim.render())?;
+                unseen = 0;
+            }
+        }
+        if unseen > 0 {
+            f(sim.render())?;
+        }
+        Ok(())
+    }
+
+    fn write_gif(
+        &self,
+        sim: &mut dyn Animate,
+        scale: usize,
+        max_cells: usize,
+        out: &mut dyn Write,
+    ) -> io::Result<()> {
+        // each frame as colour table indices, as frames can change size and colours
+        let mut table = ColourTable::default();
+        table.add(self.palette.background);
+        let (mut frames, mut cells) = (vec![], 0);
+        self.frames(sim, |frame| {
+            let (height, width) = (frame.cells.height(), frame.cells.width());
+            cells += height * width;
+            if cells > max_cells {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "{} frames come to more than {max_cells} cells, too many for a GIF, \
+                         try a larger --every or fewer --steps",
+                        frames.len() + 1
+                    ),
+                ));
+            }
+            let indices: Vec<u8> = colours(&frame, &self.palette, height, width)
+                .into_iter()
+                .map(|rgb| table.add(rgb))
+                .collect();
+            frames.push((height, width, indices));
+            Ok(())
+        })?;
+        // all drawn at the largest
+        let height = frames.iter().map(|frame| frame.0).max().unwrap_or(0);
+        let width = frames.iter().map(|frame| frame.1).max().unwrap_or(0);
+        let size = |cells: usize| {
+            u16::try_from(cells * scale).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{height}x{width} cells at scale {scale} are too large for a GIF"),
+                )
+            })
+        };
+        let (pixel_height, pixel_width) = (size(height)?, size(width)?);
+
+        let mut encoder = gif::Encoder::new(out, pixel_width, pixel_height, &table.flat())
+            .map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+        for (_, frame_width, indices) in frames {
+            // padded with the background, index 0
+            let mut padded = vec![0; height * width];
+            for (i, row) in indices.chunks(frame_width.max(1)).enumerate() {
+                padded[i * width..i * width + row.len()].copy_from_slice(row);
+            }
+            let frame = gif::Frame {
+                width: pixel_width,
+                height: pixel_height,
+                delay: self.delay,
+                buffer: Cow::Owned(scaled(&padded, width, scale)),
+                ..gif::Frame::default()
+            };
+            encoder.write_frame(&frame).map_err(io::Error::other)?;
+        }
+        Ok(())
+    }
+}
+
+/// A GIF's global colour table, the first 256 colours seen. Any more are drawn as the nearest.
+#[derive(Debug, Default)]
+struct ColourTable {
+    colours: Vec<Rgb>,
+    indices: HashMap<Rgb, u8>,
+}
+
+impl ColourTable {
+    /// The index of `rgb`, added if there's room
+    fn add(&mut self, rgb: Rgb) -> u8 {
+        if self.colours.len() < 256 && !self.indices.contains_key(&rgb) {
+            self.indices.insert(rgb, self.colours.len() as u8);
+            self.colours.push(rgb);
+        }
+        self.index(rgb)
+    }
+
+    fn index(&self, rgb: Rgb) -> u8 {
+        if let Some(&index) = self.indices.get(&rgb) {
+            return index;
+        }
+        let distance = |other: &Rgb| -> u32 {
+            (0..3)
+                .map(|c| (rgb[c].abs_diff(other[c]) as u32).pow(2))
+                .sum()
+        };
+        (0..self.colours.len())
+            .min_by_key(|&i| distance(&self.colours[i]))
+            .unwrap_or(0) as u8
+    }
+
+    fn flat(&self) -> Vec<u8> {
+        self.colours.iter().flatten().copied().collect()
+    }
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    Parse(ParseError),
+    NoAnimation { index: usize, count: usize },
+    Io(io::Error),
+}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::Parse(err) => write!(f, "invalid input, {err}"),
+            ExportError::NoAnimation { count: 0, .. } => write!(f, "there are no animations"),
+            ExportError::NoAnimation { index, count } => {
+                write!(f, "no animation {index}, expected 0 to {}", count - 1)
+            }
+            ExportError::Io(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for ExportError {}
+
+impl From<ParseError> for ExportError {
+    fn from(err: ParseError) -> Self {
+        ExportError::Parse(err)
+    }
+}
+
+impl From<io::Error> for ExportError {
+    fn from(err: io::Error) -> Self {
+        ExportError::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts up to `end`, one more `#` each step
+    struct Bar {
+        n: usize,
+        end: usize,
+    }
+
+    impl Animate for Bar {
+        fn step(&mut self) -> bool {
+            self.n += 1;
+            self.n <= self.end
+        }
+
+        fn render(&self) -> Frame {
+            let mut frame = Frame::new(1, self.n.min(self.end) + 1);
+            for j in 0..self.n.min(self.end) {
+                frame.paint((0, j), '#', Color::Red);
+            }
+            frame
+        }
+    }
+
+    #[test]
+    fn palette_from_str() {
+        let palette: Palette = "bg=#ffffff, o=ffd700".parse().unwrap();
+        assert_eq!(palette.background, [255, 255, 255]);
+        assert_eq!(palette.foreground, Palette::default().foreground);
+        assert_eq!(palette.colour(Cell::plain('o')), [255, 215, 0]);
+        assert_eq!(palette.colour(Cell::plain('.')), [255, 255, 255]);
+        assert!("o=ffd7".parse::<Palette>().is_err());
+        assert!("ab=ffd700".parse::<Palette>().is_err());
+    }
+
+    #[test]
+    fn ansi_colours() {
+        assert_eq!(rgb(Color::AnsiValue(9)), Some([255, 0, 0]));
+        assert_eq!(rgb(Color::AnsiValue(16 + 36 * 5 + 2)), Some([255, 0, 135]));
+        assert_eq!(rgb(Color::AnsiValue(255)), Some([238; 3]));
+        assert_eq!(rgb(Color::Reset), None);
+    }
+
+    #[test]
+    fn ppm_scales_cells() {
+        let mut frame = Frame::from_text("#.");
+        frame.paint((0, 0), '#', Color::Blue);
+        let mut ppm = vec![];
+        Image::new(&frame, &Palette::default(), 2)
+            .write_ppm(&mut ppm)
+            .unwrap();
+        let (blue, black) = ([0, 0, 255], [0, 0, 0]);
+        let row = [blue, blue, black, black].concat();
+        assert_eq!(ppm, [b"P6\n4 2\n255\n".to_vec(), row.clone(), row].concat());
+    }
+
+    #[test]
+    fn gif_frames() {
+        let export = Export {
+            format: Format::Gif,
+            every: 2,
+            ..Export::default()
+        };
+        let mut frames = vec![];
+        export
+            .frames(&mut Bar { n: 0, end: 5 }, |frame| {
+                frames.push(frame.to_string());
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(frames, ["\n", "##\n", "####\n", "#####\n"]);
+
+        let mut gif = vec![];
+        let mut bar = Bar { n: 0, end: 5 };
+        export.write(&mut bar, &mut gif).unwrap();
+        // run once, its last step finding it finished
+        assert_eq!(bar.n, 6);
+        let mut decoder = gif::DecodeOptions::new().read_info(gif.as_slice()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (24, 4));
+        let mut count = 0;
+        while decoder.read_next_frame().unwrap().is_some() {
+            count += 1;
+        }
+        assert_eq!(count, 4);
+    }
+
+    #[test]
+    fn gif_too_large() {
+        let export = Export {
+            format: Format::Gif,
+            ..Export::default()
+        };
+        let mut bar = Bar { n: 0, end: 100 };
+        // 1 + 2 + 3 + 4 cells, then 5 more
+        let err = export.write_gif(&mut bar, 1, 12, &mut vec![]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(err
+            .to_string()
+            .starts_with("5 frames come to more than 12 cells"));
+        assert_eq!(bar.n, 4);
+    }
+}
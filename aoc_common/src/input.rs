@@ -0,0 +1,150 @@
+use std::{
+    env,
+    error::Error,
+    fmt::{self, Display},
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+    str::FromStr,
+};
+
+/// Relative to a day's `rust` directory, where `cargo test` is run
+pub static TEST_INPUT_PATH: &str = "../test_input";
+
+/// Environment variable naming the input, used when no path is given on the command line
+pub static INPUT_ENV_VAR: &str = "AOC_INPUT";
+
+/// The `day N` directory holding a day's README and inputs, independent of the working directory
+pub fn day_dir(day: u8) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("day {day}"))
+}
+
+/// The puzzle input saved next to a day's README
+pub fn default_path(day: u8) -> PathBuf {
+    day_dir(day).join("input")
+}
+
+/// The README's example, as used by each day's tests
+pub fn example_path(day: u8) -> PathBuf {
+    day_dir(day).join("test_input")
+}
+
+/// Reads a file which is expected to exist, such as a test input
+pub fn read(path: &str) -> String {
+    fs::read_to_string(path).unwrap_or_else(|err| panic!("Could not read {path}: {err}"))
+}
+
+pub fn test_input() -> String {
+    read(TEST_INPUT_PATH)
+}
+
+/// Where a puzzle input is read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Path(PathBuf),
+    Stdin,
+}
+
+impl FromStr for InputSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err("Empty input path".to_string()),
+            "-" => Ok(InputSource::Stdin),
+            _ => Ok(InputSource::Path(PathBuf::from(s))),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Path(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+impl InputSource {
+    /// A command line argument takes precedence over `AOC_INPUT`, which takes precedence over `default`
+    pub fn resolve(arg: Option<InputSource>, default: PathBuf) -> Result<Self, InputError> {
+        if let Some(source) = arg {
+            return Ok(source);
+        }
+        match env::var(INPUT_ENV_VAR) {
+            Ok(var) => var.parse().map_err(|reason| InputError {
+                input: InputSource::Path(PathBuf::from(&var)),
+                err: io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{INPUT_ENV_VAR}: {reason}"),
+                ),
+            }),
+            Err(env::VarError::NotPresent) => Ok(InputSource::Path(default)),
+            Err(err) => Err(InputError {
+                input: InputSource::Path(default),
+                err: io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{INPUT_ENV_VAR}: {err}"),
+                ),
+            }),
+        }
+    }
+
+    pub fn read(&self) -> Result<String, InputError> {
+        let contents = match self {
+            InputSource::Path(path) => fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut contents = String::new();
+                io::stdin().read_to_string(&mut contents).map(|_| contents)
+            }
+        };
+        contents.map_err(|err| InputError {
+            input: self.clone(),
+            err,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    pub input: InputSource,
+    pub err: io::Error,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Could not read {}: {}", self.input, self.err)
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dash_is_stdin() {
+        assert_eq!("-".parse::<InputSource>(), Ok(InputSource::Stdin));
+        assert_eq!(
+            "../input".parse::<InputSource>(),
+            Ok(InputSource::Path(PathBuf::from("../input")))
+        );
+    }
+
+    #[test]
+    fn missing_file_names_path() {
+        let source = InputSource::Path(PathBuf::from("no/such/input"));
+        let err = source.read().unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Could not read no/such/input: "));
+    }
+}
@@ -0,0 +1,369 @@
+//! Closed intervals of integers, and sets of them kept sorted and merged
+
+use std::{
+    fmt::{self, Debug, Display},
+    str::FromStr,
+};
+
+use crate::parse::{self, ParseError};
+
+/// An integer type intervals can be made of
+pub trait Endpoint: Copy + Ord + Debug {
+    /// The next value up, None at the maximum
+    fn succ(self) -> Option<Self>;
+    /// The next value down, None at the minimum
+    fn pred(self) -> Option<Self>;
+    /// Number of values from `low` to `high` inclusive, which must be in order
+    fn span(low: Self, high: Self) -> u64;
+}
+
+macro_rules! endpoint {
+    ($($t:ty),*) => {$(
+        impl Endpoint for $t {
+            fn succ(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn pred(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            fn span(low: Self, high: Self) -> u64 {
+                (high as i128 - low as i128 + 1).min(u64::MAX as i128) as u64
+            }
+        }
+    )*};
+}
+
+endpoint!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// The values from `low` to `high` inclusive, empty if `high` is below `low`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub low: T,
+    pub high: T,
+}
+
+impl<T: Endpoint> Interval<T> {
+    pub fn new(low: T, high: T) -> Self {
+        Interval { low, high }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.high < self.low
+    }
+
+    /// Number of values in the interval, saturating at `u64::MAX`
+    pub fn len(&self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            T::span(self.low, self.high)
+        }
+    }
+
+    pub fn contains(&self, p: T) -> bool {
+        self.low <= p && p <= self.high
+    }
+
+    /// Whether every value of `other` is also in this interval
+    pub fn encloses(&self, other: &Self) -> bool {
+        other.is_empty() || (self.low <= other.low && other.high <= self.high)
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// Values in both intervals, which may be empty
+    pub fn intersection(&self, other: &Self) -> Self {
+        Interval::new(self.low.max(other.low), self.high.min(other.high))
+    }
+
+    /// Whether the two can be merged into one interval, overlapping or with nothing between them
+    fn joins(&self, other: &Self) -> bool {
+        let (first, second) = if self.low <= other.low {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        first.high >= second.low || first.high.succ() == Some(second.low)
+    }
+}
+
+/// `low-high`, where either may be negative, e.g. `-3--1`
+impl<T> FromStr for Interval<T>
+where
+    T: Endpoint + FromStr,
+    T::Err: Display,
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // the separator is the first '-' after the start of the low end
+        match s.char_indices().skip(1).find(|&(_, c)| c == '-') {
+            Some((i, _)) => Ok(Interval::new(
+                parse::field(s, &s[..i])?,
+                parse::field(s, &s[i + 1..])?,
+            )),
+            None => Err(ParseError::new(s, s, "expected \"low-high\" range")),
+        }
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.low, self.high)
+    }
+}
+
+/// A set of values, stored as the fewest intervals covering them: sorted, non-empty, and neither
+/// overlapping nor adjacent
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Endpoint> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet::new()
+    }
+}
+
+impl<T: Endpoint> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        IntervalSet::merged(vec![interval])
+    }
+}
+
+impl<T: Endpoint> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        IntervalSet::merged(iter.into_iter().collect())
+    }
+}
+
+impl<T: Endpoint> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { intervals: vec![] }
+    }
+
+    /// Merges overlapping and adjacent intervals, dropping empty ones
+    pub fn merged(mut intervals: Vec<Interval<T>>) -> Self {
+        intervals.retain(|interval| !interval.is_empty());
+        intervals.sort_unstable_by_key(|interval| interval.low);
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if last.joins(&interval) => last.high = last.high.max(interval.high),
+                _ => merged.push(interval),
+            }
+        }
+        IntervalSet { intervals: merged }
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of values covered, saturating at `u64::MAX`
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().fold(0, |total: u64, interval| {
+            total.saturating_add(interval.len())
+        })
+    }
+
+    pub fn contains(&self, p: T) -> bool {
+        // the last interval starting at or before p
+        let i = self.intervals.partition_point(|interval| interval.low <= p);
+        i > 0 && self.intervals[i - 1].contains(p)
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        // intervals before `start` and from `end` on are untouched
+        let start = self
+            .intervals
+            .partition_point(|other| other.high < interval.low && !other.joins(&interval));
+        let end = self
+            .intervals
+            .partition_point(|other| other.low <= interval.high || other.joins(&interval));
+        let merged = self.intervals[start..end]
+            .iter()
+            .fold(interval, |merged, other| {
+                Interval::new(merged.low.min(other.low), merged.high.max(other.high))
+            });
+        self.intervals.splice(start..end, [merged]);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        IntervalSet::merged([&self.intervals[..], &other.intervals[..]].concat())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut i, mut j) = (0, 0);
+        let mut intervals = vec![];
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[j]);
+            let both = a.intersection(&b);
+            if !both.is_empty() {
+                intervals.push(both);
+            }
+            // the one ending first can't overlap anything further on
+            if a.high < b.high {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    /// Values in this set but not `other`
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let mut j = 0;
+        for &interval in &self.intervals {
+            let mut rest = Some(interval);
+            // skip those of `other` entirely before this interval
+            while j < other.intervals.len() && other.intervals[j].high < interval.low {
+                j += 1;
+            }
+            let mut k = j;
+            while let (Some(left), Some(cut)) = (rest, other.intervals.get(k)) {
+                if cut.low > left.high {
+                    break;
+                }
+                if let Some(before) = cut.low.pred().filter(|&before| before >= left.low) {
+                    intervals.push(Interval::new(left.low, before));
+                }
+                rest = cut
+                    .high
+                    .succ()
+                    .filter(|&after| after <= left.high)
+                    .map(|after| Interval::new(after, left.high));
+                k += 1;
+            }
+            intervals.extend(rest);
+        }
+        IntervalSet { intervals }
+    }
+
+    /// Values of `bounds` not in the set
+    pub fn gaps(&self, bounds: Interval<T>) -> Self {
+        IntervalSet::from(bounds).difference(self)
+    }
+}
+
+/// The first of the stretches covered by the most of `intervals`, and how many cover it
+pub fn max_coverage<T: Endpoint>(
+    intervals: impl IntoIterator<Item = Interval<T>>,
+) -> Option<(Interval<T>, usize)> {
+    // at the same value starts come first, so intervals ending there still count
+    let mut events: Vec<(T, bool)> = intervals
+        .into_iter()
+        .filter(|interval| !interval.is_empty())
+        .flat_map(|interval| [(interval.low, false), (interval.high, true)])
+        .collect();
+    events.sort_unstable();
+
+    let (mut depth, mut most) = (0, 0);
+    let mut best = None;
+    // where the deepest stretch so far started, until the next interval ends
+    let mut rising = None;
+    for (p, is_end) in events {
+        if is_end {
+            if let Some(low) = rising.take() {
+                best = Some((Interval::new(low, p), depth));
+            }
+            depth -= 1;
+        } else {
+            depth += 1;
+            if depth > most {
+                most = depth;
+                rising = Some(p);
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals
+            .iter()
+            .map(|&(low, high)| Interval::new(low, high))
+            .collect()
+    }
+
+    fn pairs(set: &IntervalSet<i32>) -> Vec<(i32, i32)> {
+        set.intervals()
+            .iter()
+            .map(|interval| (interval.low, interval.high))
+            .collect()
+    }
+
+    #[test]
+    fn parse_and_compare() {
+        let a: Interval<i32> = "-3--1".parse().unwrap();
+        assert_eq!(a, Interval::new(-3, -1));
+        assert_eq!(a.len(), 3);
+        let err = "2-x5".parse::<Interval<u32>>().unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (3, "x5"));
+
+        let b = Interval::new(-1, 4);
+        assert!(a.overlaps(&b) && !a.encloses(&b));
+        assert!(Interval::new(-5, 5).encloses(&b));
+        assert!(!a.overlaps(&Interval::new(0, 2)));
+        assert_eq!(Interval::new(u8::MIN, u8::MAX).len(), 256);
+    }
+
+    #[test]
+    fn merging_and_insert() {
+        let merged = set(&[(5, 7), (1, 2), (3, 3), (10, 12), (11, 20), (30, 29)]);
+        assert_eq!(pairs(&merged), [(1, 3), (5, 7), (10, 20)]);
+        assert_eq!(merged.len(), 3 + 3 + 11);
+        assert!(merged.contains(7) && !merged.contains(8) && !merged.contains(0));
+
+        let mut inserted = merged.clone();
+        inserted.insert(Interval::new(4, 4));
+        assert_eq!(pairs(&inserted), [(1, 7), (10, 20)]);
+        inserted.insert(Interval::new(25, 26));
+        inserted.insert(Interval::new(-5, -4));
+        inserted.insert(Interval::new(8, 9));
+        assert_eq!(pairs(&inserted), [(-5, -4), (1, 20), (25, 26)]);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25), (28, 28), (40, 50)]);
+        assert_eq!(pairs(&a.union(&b)), [(0, 30), (40, 50)]);
+        assert_eq!(pairs(&a.intersection(&b)), [(5, 10), (20, 25), (28, 28)]);
+        assert_eq!(pairs(&a.difference(&b)), [(0, 4), (26, 27), (29, 30)]);
+        assert_eq!(pairs(&b.difference(&a)), [(11, 19), (40, 50)]);
+        assert_eq!(pairs(&a.gaps(Interval::new(-2, 22))), [(-2, -1), (11, 19)]);
+        assert!(a.difference(&a).is_empty());
+
+        let edges = IntervalSet::from(Interval::new(0u8, 255));
+        let middle = IntervalSet::from(Interval::new(1u8, 254));
+        assert_eq!(edges.difference(&middle).len(), 2);
+    }
+
+    #[test]
+    fn deepest_coverage() {
+        let intervals = [(1, 5), (3, 8), (4, 6), (8, 9), (8, 8)].map(|(l, h)| Interval::new(l, h));
+        assert_eq!(max_coverage(intervals), Some((Interval::new(4, 5), 3)));
+        let touching = [(1, 3), (3, 4)].map(|(l, h)| Interval::new(l, h));
+        assert_eq!(max_coverage(touching), Some((Interval::new(3, 3), 2)));
+        assert_eq!(max_coverage(Vec::<Interval<i32>>::new()), None);
+    }
+}
@@ -0,0 +1,252 @@
+//! An index over many intervals for finding those overlapping a query, and sweeps over all of
+//! them at once
+
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::interval::{Endpoint, Interval};
+
+/// An interval and the value it carries
+pub type Entry<T, V> = (Interval<T>, V);
+
+/// Two overlapping entries
+pub type Pair<'a, T, V> = (&'a Entry<T, V>, &'a Entry<T, V>);
+
+/// A static interval tree, each interval carrying a value such as where it came from. Built in
+/// O(n log n), it finds the k intervals overlapping a query in O(log n + k log n) at worst.
+#[derive(Debug, Clone)]
+pub struct IntervalTree<T, V> {
+    /// Sorted by low end, as an implicit balanced tree: the middle of each slice is its root
+    entries: Vec<Entry<T, V>>,
+    /// The highest high end in the subtree rooted at each entry
+    max_high: Vec<T>,
+}
+
+impl<T: Endpoint, V> FromIterator<Entry<T, V>> for IntervalTree<T, V> {
+    fn from_iter<I: IntoIterator<Item = Entry<T, V>>>(iter: I) -> Self {
+        IntervalTree::new(iter.into_iter().collect())
+    }
+}
+
+impl<T: Endpoint, V> IntervalTree<T, V> {
+    /// Empty intervals are left out, as they overlap nothing
+    pub fn new(mut entries: Vec<Entry<T, V>>) -> Self {
+        entries.retain(|(interval, _)| !interval.is_empty());
+        entries.sort_by_key(|(interval, _)| interval.low);
+        let mut max_high: Vec<T> = entries.iter().map(|(interval, _)| interval.high).collect();
+        fill_max(&mut max_high, 0, entries.len());
+        IntervalTree { entries, max_high }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Every interval and its value, sorted by low end
+    pub fn entries(&self) -> &[Entry<T, V>] {
+        &self.entries
+    }
+
+    /// Intervals sharing at least one value with `query`, sorted by low end
+    pub fn overlapping(&self, query: Interval<T>) -> Vec<&Entry<T, V>> {
+        let mut found = vec![];
+        if !query.is_empty() {
+            self.search(0, self.entries.len(), query, &mut found);
+        }
+        found
+    }
+
+    /// Intervals containing `p`
+    pub fn stabbing(&self, p: T) -> Vec<&Entry<T, V>> {
+        self.overlapping(Interval::new(p, p))
+    }
+
+    /// Intervals containing all of `query`
+    pub fn enclosing(&self, query: Interval<T>) -> Vec<&Entry<T, V>> {
+        let mut found = self.overlapping(query);
+        found.retain(|(interval, _)| interval.encloses(&query));
+        found
+    }
+
+    /// In order, within the subtree of `entries[start..end]`
+    fn search<'a>(
+        &'a self,
+        start: usize,
+        end: usize,
+        query: Interval<T>,
+        found: &mut Vec<&'a Entry<T, V>>,
+    ) {
+        if start >= end {
+            return;
+        }
+        let mid = start + (end - start) / 2;
+        // nothing below here reaches the query
+        if self.max_high[mid] < query.low {
+            return;
+        }
+        self.search(start, mid, query, found);
+        let entry = &self.entries[mid];
+        // everything to the right starts after the query ends
+        if entry.0.low > query.high {
+            return;
+        }
+        if entry.0.overlaps(&query) {
+            found.push(entry);
+        }
+        self.search(mid + 1, end, query, found);
+    }
+
+    /// Every pair of overlapping intervals, each once with the earlier starting first, found by
+    /// sweeping across them in O(n log n + k)
+    pub fn overlapping_pairs(&self) -> Vec<Pair<'_, T, V>> {
+        let mut pairs = vec![];
+        // indices of intervals that might still overlap the next, soonest ending on top
+        let mut active: BinaryHeap<Reverse<(T, usize)>> = BinaryHeap::new();
+        for (i, entry) in self.entries.iter().enumerate() {
+            while active
+                .peek()
+                .is_some_and(|Reverse((high, _))| *high < entry.0.low)
+            {
+                active.pop();
+            }
+            pairs.extend(
+                active
+                    .iter()
+                    .map(|Reverse((_, j))| (&self.entries[*j], entry)),
+            );
+            active.push(Reverse((entry.0.high, i)));
+        }
+        pairs
+    }
+
+    /// Intervals enclosed by another, in O(n log n). Of identical intervals all but the first
+    /// (in order of low end, then as given) are redundant.
+    pub fn redundant(&self) -> Vec<&Entry<T, V>> {
+        // widest first among those starting together, so it's seen before what it encloses
+        let mut order: Vec<usize> = (0..self.entries.len()).collect();
+        order.sort_by_key(|&i| (self.entries[i].0.low, Reverse(self.entries[i].0.high)));
+        let mut reach: Option<T> = None;
+        let mut found = vec![];
+        for i in order {
+            let entry = &self.entries[i];
+            if reach.is_some_and(|reach| reach >= entry.0.high) {
+                found.push(entry);
+            }
+            reach = reach.max(Some(entry.0.high));
+        }
+        found
+    }
+}
+
+/// Turns each of `max_high[start..end]`, the high end of its own interval to begin with, into
+/// the highest in its subtree. Returns the subtree's highest.
+fn fill_max<T: Endpoint>(max_high: &mut [T], start: usize, end: usize) -> Option<T> {
+    if start >= end {
+        return None;
+    }
+    let mid = start + (end - start) / 2;
+    let left = fill_max(max_high, start, mid);
+    let right = fill_max(max_high, mid + 1, end);
+    let highest = [left, right]
+        .into_iter()
+        .flatten()
+        .fold(max_high[mid], T::max);
+    max_high[mid] = highest;
+    Some(highest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pseudo-random intervals within 0 to 200, numbered in order
+    fn intervals(n: usize) -> Vec<Entry<u32, usize>> {
+        let mut state = 12345u64;
+        let mut next = move |below: u64| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 33) % below) as u32
+        };
+        (0..n)
+            .map(|i| {
+                let low = next(200);
+                (Interval::new(low, low + next(20)), i)
+            })
+            .collect()
+    }
+
+    fn values<'a>(entries: impl IntoIterator<Item = &'a Entry<u32, usize>>) -> Vec<usize> {
+        let mut values: Vec<usize> = entries.into_iter().map(|(_, v)| *v).collect();
+        values.sort();
+        values
+    }
+
+    #[test]
+    fn queries_match_brute_force() {
+        let all = intervals(300);
+        let tree: IntervalTree<u32, usize> = all.iter().copied().collect();
+        for query in [
+            (0, 0),
+            (50, 60),
+            (100, 100),
+            (190, 250),
+            (219, 219),
+            (300, 400),
+        ] {
+            let query = Interval::new(query.0, query.1);
+            let expected = values(all.iter().filter(|(interval, _)| interval.overlaps(&query)));
+            assert_eq!(values(tree.overlapping(query)), expected);
+            let enclosing = values(all.iter().filter(|(interval, _)| interval.encloses(&query)));
+            assert_eq!(values(tree.enclosing(query)), enclosing);
+        }
+        assert_eq!(
+            values(tree.stabbing(42)),
+            values(all.iter().filter(|(interval, _)| interval.contains(42)))
+        );
+        assert!(tree.overlapping(Interval::new(5, 4)).is_empty());
+    }
+
+    #[test]
+    fn sweeps_match_brute_force() {
+        let all = intervals(200);
+        let tree: IntervalTree<u32, usize> = all.iter().copied().collect();
+
+        let mut pairs: Vec<(usize, usize)> = tree
+            .overlapping_pairs()
+            .iter()
+            .map(|((_, a), (_, b))| (*a.min(b), *a.max(b)))
+            .collect();
+        pairs.sort();
+        let mut expected = vec![];
+        for (i, (a, _)) in all.iter().enumerate() {
+            for (j, (b, _)) in all.iter().enumerate().skip(i + 1) {
+                if a.overlaps(b) {
+                    expected.push((i, j));
+                }
+            }
+        }
+        assert_eq!(pairs, expected);
+
+        let enclosed = |i: usize| {
+            let (a, _) = all[i];
+            all.iter().enumerate().any(|(j, (b, _))| {
+                j != i && b.encloses(&a) && (*b != a || tree_first(&tree, a) != i)
+            })
+        };
+        let expected: Vec<usize> = (0..all.len()).filter(|&i| enclosed(i)).collect();
+        assert_eq!(values(tree.redundant()), expected);
+    }
+
+    /// Which of identical intervals the tree keeps
+    fn tree_first(tree: &IntervalTree<u32, usize>, interval: Interval<u32>) -> usize {
+        tree.entries()
+            .iter()
+            .find(|(other, _)| *other == interval)
+            .unwrap()
+            .1
+    }
+}
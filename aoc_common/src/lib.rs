@@ -0,0 +1,11 @@
+pub mod animation;
+pub mod examples;
+pub mod graph;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod interval;
+pub mod interval_tree;
+pub mod parse;
+pub mod point;
+pub mod solution;
@@ -0,0 +1,163 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// Where and why some input failed to parse. Lines and columns count from 1, or are both 0 for
+/// an option given alongside the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    /// Locates `text` within `source`, which it should be a slice of. Otherwise the error points
+    /// at the end of `source`, e.g. when `text` is missing altogether.
+    pub fn new(source: &str, text: &str, reason: impl Display) -> Self {
+        let start = text.as_ptr() as usize;
+        let offset = match start.checked_sub(source.as_ptr() as usize) {
+            Some(offset) if offset + text.len() <= source.len() => offset,
+            _ => source.len(),
+        };
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: text.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// For an option's `value` that the day can't use
+    pub fn option(name: &str, value: &str, reason: impl Display) -> Self {
+        ParseError {
+            line: 0,
+            column: 0,
+            text: value.to_string(),
+            reason: format!("option {name}: {reason}"),
+        }
+    }
+
+    /// For errors from parsing a part of the input that starts `lines` lines in
+    pub fn offset_lines(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+
+    /// For errors from parsing `part`, a slice of `source`, makes the position relative to `source`
+    pub fn within(self, source: &str, part: &str) -> Self {
+        let start = ParseError::new(source, part, "");
+        ParseError {
+            line: start.line + self.line - 1,
+            column: if self.line == 1 { start.column + self.column - 1 } else { self.column },
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.reason)?;
+        } else {
+            write!(f, "line {}, column {}: {}", self.line, self.column, self.reason)?;
+        }
+        if !self.text.is_empty() {
+            write!(f, " (found {:?})", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `text`, a slice of `source`, reporting any failure at its position
+pub fn field<T>(source: &str, text: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    text.parse().map_err(|err| ParseError::new(source, text, err))
+}
+
+/// The part of `line` after `prefix`, which it must start with
+pub fn after<'a>(line: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    line.strip_prefix(prefix)
+        .ok_or_else(|| ParseError::new(line, line, format!("expected line starting {prefix:?}")))
+}
+
+/// Parses each line of `input` in turn, stopping at the first error
+pub fn lines<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| line.parse().map_err(|err: ParseError| err.offset_lines(i)))
+        .collect()
+}
+
+/// Parses each blank line separated block of `input` in turn, stopping at the first error
+pub fn blocks<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    let mut start = 0;
+    input
+        .split("\n\n")
+        .map(|block| {
+            let parsed = block.parse().map_err(|err: ParseError| err.offset_lines(start));
+            start += block.matches('\n').count() + 2;
+            parsed
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_text_in_source() {
+        let source = "noop\naddx 3\naddx x";
+        let err = ParseError::new(source, &source[17..], "not a number");
+        assert_eq!((err.line, err.column), (3, 6));
+        assert_eq!(err.to_string(), "line 3, column 6: not a number (found \"x\")");
+
+        let err = ParseError::new(source, "elsewhere", "missing");
+        assert_eq!((err.line, err.column), (3, 7));
+
+        let part = &source[12..];
+        let err = ParseError::new(part, &part[5..], "not a number").within(source, part);
+        assert_eq!((err.line, err.column), (3, 6));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Num(u32);
+
+    impl FromStr for Num {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let (_, n) = s.split_once(' ').unwrap_or(("", s));
+            field(s, n).map(Num)
+        }
+    }
+
+    #[test]
+    fn offsets_lines_and_blocks() {
+        assert_eq!(lines("1\n2"), Ok(vec![Num(1), Num(2)]));
+
+        let err = lines::<Num>("1\n2\nn three").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 3, "three"));
+
+        let err = blocks::<Num>("1\n\n2\n\nn 3x").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (5, 3, "3x"));
+    }
+}
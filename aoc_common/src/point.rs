@@ -0,0 +1,53 @@
+use std::{
+    cmp::{max, min},
+    str::FromStr,
+};
+
+use crate::parse::{self, ParseError};
+
+#[derive(Debug, Clone, Copy, Default, Hash, Eq, PartialEq)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl FromStr for Point {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(',') {
+            Some((xs, ys)) => Ok(Point {
+                x: parse::field(s, xs)?,
+                y: parse::field(s, ys)?,
+            }),
+            None => Err(ParseError::new(s, s, "expected \"x,y\"")),
+        }
+    }
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+
+    /// Manhattan distance
+    pub fn dist_to(&self, other: Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// bounding box top left
+    pub fn bb_tl(&self, other: Point) -> Point {
+        Point {
+            x: min(self.x, other.x),
+            y: min(self.y, other.y),
+        }
+    }
+
+    /// bounding box bottom right
+    pub fn bb_br(&self, other: Point) -> Point {
+        Point {
+            x: max(self.x, other.x),
+            y: max(self.y, other.y),
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::{self, Display},
+    io::Write,
+    process::ExitCode,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    animation::{self, Animate},
+    image::{Export, ExportError},
+    input::{self, InputSource},
+    parse::ParseError,
+};
+
+/// A day's puzzle, where the input is parsed once and shared between both parts
+pub trait Solution {
+    const DAY: u8;
+    type Parsed;
+
+    /// Names of the options the day can be run with, as well as its input
+    const OPTIONS: &'static [&'static str] = &[];
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError>;
+
+    /// Parses the input to be solved as `options` say, which are only those in `OPTIONS`
+    fn parse_with(input: &str, _options: &Options) -> Result<Self::Parsed, ParseError> {
+        Self::parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> String;
+    fn part2(parsed: &Self::Parsed) -> String;
+
+    /// A report on the input beyond the answers, for days that have one
+    fn analyse(_parsed: &Self::Parsed) -> Option<String> {
+        None
+    }
+
+    /// Simulations of the puzzle, for days that have them, to be played or exported as images
+    fn animations(_parsed: &Self::Parsed) -> Vec<Box<dyn Animate + '_>> {
+        vec![]
+    }
+
+    /// Prints a picture of the solved puzzle, for days that have one. Plays each animation in
+    /// turn unless overridden.
+    fn visualise(parsed: &Self::Parsed) {
+        for mut sim in Self::animations(parsed) {
+            animation::play(sim.as_mut());
+        }
+    }
+}
+
+/// Settings for a day beyond its input, by name, e.g. which crane day 5 uses
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options(BTreeMap<String, String>);
+
+impl Options {
+    pub fn set(&mut self, name: &str, value: impl Display) {
+        self.0.insert(name.to_string(), value.to_string());
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    /// Option `name` parsed as a `T`, None if it wasn't given
+    pub fn parse<T>(&self, name: &str) -> Result<Option<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.get(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|err| ParseError::option(name, value, err))
+            })
+            .transpose()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("Expected part 1 or 2, got {s}")),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PartResult {
+    pub part: Part,
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug)]
+pub struct Report {
+    pub day: u8,
+    pub parse_elapsed: Duration,
+    pub parts: Vec<PartResult>,
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Day {}", self.day)?;
+        writeln!(f, " parsed ({:?})", self.parse_elapsed)?;
+        for PartResult {
+            part,
+            answer,
+            elapsed,
+        } in &self.parts
+        {
+            // multi line answers (e.g. pictures of letters) start on their own line
+            if answer.contains('\n') {
+                writeln!(f, " part {part} ({elapsed:?}):\n{answer}")?;
+            } else {
+                writeln!(f, " part {part} ({elapsed:?}): {answer}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+/// Parses the input with `options`, having checked the day takes each of them
+fn parse<S: Solution>(input: &str, options: &Options) -> Result<S::Parsed, ParseError> {
+    if let Some((name, value)) = options
+        .0
+        .iter()
+        .find(|(name, _)| !S::OPTIONS.contains(&name.as_str()))
+    {
+        return Err(ParseError::option(
+            name,
+            value,
+            format!("day {} has no such option", S::DAY),
+        ));
+    }
+    S::parse_with(input, options)
+}
+
+/// Parses the input once, then runs each requested part on it
+pub fn run<S: Solution>(
+    input: &str,
+    parts: &[Part],
+    options: &Options,
+) -> Result<Report, ParseError> {
+    let (parsed, parse_elapsed) = timed(|| parse::<S>(input, options));
+    let parsed = parsed?;
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let (answer, elapsed) = timed(|| match part {
+                Part::One => S::part1(&parsed),
+                Part::Two => S::part2(&parsed),
+            });
+            PartResult {
+                part,
+                answer,
+                elapsed,
+            }
+        })
+        .collect();
+    Ok(Report {
+        day: S::DAY,
+        parse_elapsed,
+        parts,
+    })
+}
+
+/// Parses and analyses the input, for days that have an analysis
+pub fn analyse<S: Solution>(input: &str, options: &Options) -> Result<Option<String>, ParseError> {
+    Ok(S::analyse(&parse::<S>(input, options)?))
+}
+
+/// Parses and visualises the input, for days that have a visualisation
+pub fn visualise<S: Solution>(input: &str, options: &Options) -> Result<(), ParseError> {
+    S::visualise(&parse::<S>(input, options)?);
+    Ok(())
+}
+
+/// Parses the input and writes the day's `index`th animation as an image
+pub fn export<S: Solution>(
+    input: &str,
+    index: usize,
+    options: &Options,
+    export: &Export,
+    out: &mut dyn Write,
+) -> Result<(), ExportError> {
+    let parsed = parse::<S>(input, options)?;
+    let mut sims = S::animations(&parsed);
+    if index >= sims.len() {
+        return Err(ExportError::NoAnimation {
+            index,
+            count: sims.len(),
+        });
+    }
+    export.write(sims.swap_remove(index).as_mut(), out)?;
+    Ok(())
+}
+
+/// Entry point of each day's own binary, taking the input path (or `-` for stdin) as its only
+/// argument, and running the day without options
+pub fn main<S: Solution>() -> ExitCode {
+    let arg = match env::args().nth(1).map(|arg| arg.parse()).transpose() {
+        Ok(arg) => arg,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let contents = match InputSource::resolve(arg, input::default_path(S::DAY))
+        .and_then(|source| source.read())
+    {
+        Ok(contents) => contents,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let options = Options::default();
+    match run::<S>(&contents, &Part::BOTH, &options).and_then(|report| {
+        print!("{report}");
+        print!("{}", analyse::<S>(&contents, &options)?.unwrap_or_default());
+        visualise::<S>(&contents, &options)
+    }) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Invalid day {} input, {err}", S::DAY);
+            ExitCode::FAILURE
+        }
+    }
+}
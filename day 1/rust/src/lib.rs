@@ -0,0 +1,264 @@
+//! Day 1: Calorie Counting
+
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    error::Error,
+    fmt::{self, Display},
+    io::{self, BufRead},
+};
+use aoc_common::{parse::ParseError, solution::{Options, Solution}};
+
+/// Elves added up by part 2, unless run with the `top` option
+pub const TOP: usize = 3;
+
+/// Elves' totals, added up as their snacks are read. A total is kept for each elf, not each snack,
+/// with the `k` largest in a heap of their own.
+#[derive(Debug, Clone)]
+pub struct Tally {
+    k: usize,
+    /// The largest totals so far, smallest on top to be replaced first
+    top: BinaryHeap<Reverse<u64>>,
+    median: Median,
+    elves: usize,
+    items: usize,
+    sum: u64,
+    /// Total of the elf being read, None between elves
+    current: Option<u64>,
+}
+
+/// Running median, the smaller half of the totals in a max heap and the larger half in a min heap
+#[derive(Debug, Clone, Default)]
+struct Median {
+    /// Holds the middle total when there's an odd number
+    lower: BinaryHeap<u64>,
+    upper: BinaryHeap<Reverse<u64>>,
+}
+
+impl Median {
+    fn push(&mut self, total: u64) {
+        if self.lower.peek().is_some_and(|&max| total > max) {
+            self.upper.push(Reverse(total));
+        } else {
+            self.lower.push(total);
+        }
+        if self.lower.len() > self.upper.len() + 1 {
+            self.upper.extend(self.lower.pop().map(Reverse));
+        } else if self.upper.len() > self.lower.len() {
+            self.lower.extend(self.upper.pop().map(|Reverse(total)| total));
+        }
+    }
+
+    fn get(&self) -> f64 {
+        match (self.lower.peek(), self.upper.peek()) {
+            (Some(&lower), Some(&Reverse(upper))) if self.lower.len() == self.upper.len() => {
+                (lower as f64 + upper as f64) / 2.0
+            }
+            (Some(&lower), _) => lower as f64,
+            (None, _) => 0.0
+        }
+    }
+}
+
+/// Summary of every elf's total calories
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub elves: usize,
+    /// Snacks carried by all the elves together
+    pub items: usize,
+    pub mean: f64,
+    pub median: f64,
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} elves carrying {} items, mean {:.1} calories, median {:.1}",
+               self.elves, self.items, self.mean, self.median)
+    }
+}
+
+impl Tally {
+    /// Ranks the `k` largest totals. Panics if `k` is 0.
+    pub fn new(k: usize) -> Self {
+        assert!(k > 0, "no totals to rank");
+        Tally { k, top: BinaryHeap::with_capacity(k + 1), median: Median::default(), elves: 0, items: 0, sum: 0, current: None }
+    }
+
+    pub fn add_item(&mut self, calories: u64) {
+        *self.current.get_or_insert(0) += calories;
+        self.items += 1;
+    }
+
+    /// Finishes the current elf, if any. Repeated blank lines don't make empty elves.
+    pub fn end_elf(&mut self) {
+        let Some(total) = self.current.take() else { return };
+        self.elves += 1;
+        self.sum += total;
+        self.median.push(total);
+        self.top.push(Reverse(total));
+        if self.top.len() > self.k {
+            self.top.pop();
+        }
+    }
+
+    /// Reads a line of the input: calories, or a blank line between elves
+    pub fn line(&mut self, line: &str) -> Result<(), ParseError> {
+        let calories = line.trim();
+        if calories.is_empty() {
+            self.end_elf();
+            return Ok(());
+        }
+        let calories = calories.parse()
+            .map_err(|_| ParseError::new(line, line, "expected calories, or a blank line between elves"))?;
+        self.add_item(calories);
+        Ok(())
+    }
+
+    /// The `k` largest totals, largest first. Includes the elf being read.
+    pub fn top(&self) -> Vec<u64> {
+        let mut top: Vec<u64> = self.top.iter().map(|&Reverse(total)| total)
+            .chain(self.current)
+            .collect();
+        top.sort_unstable_by(|a, b| b.cmp(a));
+        top.truncate(self.k);
+        top
+    }
+
+    /// Includes the elf being read
+    pub fn stats(&self) -> Stats {
+        let elves = self.elves + self.current.iter().count();
+        let sum = self.sum + self.current.unwrap_or(0);
+        let median = match self.current {
+            Some(total) => {
+                let mut median = self.median.clone();
+                median.push(total);
+                median.get()
+            }
+            None => self.median.get()
+        };
+        Stats {
+            elves,
+            items: self.items,
+            mean: if elves == 0 { 0.0 } else { sum as f64 / elves as f64 },
+            median
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(err) => write!(f, "{err}"),
+            ReadError::Parse(err) => write!(f, "{err}")
+        }
+    }
+}
+
+impl Error for ReadError {}
+
+/// Tallies the elves a line at a time, without holding the whole input, ranking the `k` largest
+pub fn read(reader: impl BufRead, k: usize) -> Result<Tally, ReadError> {
+    let mut tally = Tally::new(k);
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.map_err(ReadError::Io)?;
+        tally.line(&line).map_err(|err| ReadError::Parse(err.offset_lines(i)))?;
+    }
+    tally.end_elf();
+    Ok(tally)
+}
+
+/// Total calories carried by each elf, ranking the largest `k`
+pub fn parse(contents: &str, k: usize) -> Result<Tally, ParseError> {
+    read(contents.as_bytes(), k).map_err(|err| match err {
+        ReadError::Parse(err) => err,
+        ReadError::Io(err) => unreachable!("reading from memory failed, {err}")
+    })
+}
+
+/// Calories carried by the elf carrying the most
+pub fn part1(tally: &Tally) -> u64 {
+    tally.top().first().copied().unwrap_or(0)
+}
+
+/// Calories carried by the top `k` elves together, three in the puzzle
+pub fn part2(tally: &Tally) -> u64 {
+    tally.top().iter().sum()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    type Parsed = Tally;
+    const OPTIONS: &'static [&'static str] = &["top"];
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input, TOP)
+    }
+
+    fn parse_with(input: &str, options: &Options) -> Result<Self::Parsed, ParseError> {
+        match options.parse("top")? {
+            Some(0) => Err(ParseError::option("top", "0", "part 2 needs at least one elf")),
+            k => parse(input, k.unwrap_or(TOP))
+        }
+    }
+
+    fn part1(tally: &Self::Parsed) -> String {
+        part1(tally).to_string()
+    }
+
+    fn part2(tally: &Self::Parsed) -> String {
+        part2(tally).to_string()
+    }
+
+    fn analyse(tally: &Self::Parsed) -> Option<String> {
+        Some(format!("{}\n", tally.stats()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input;
+    use crate::*;
+
+    #[test]
+    fn top_k_and_stats() {
+        let tally = read(input::test_input().as_bytes(), 2).unwrap();
+        assert_eq!(tally.top(), vec![24000, 11000]);
+        assert_eq!(tally.stats(), Stats { elves: 5, items: 10, mean: 11000.0, median: 10000.0 });
+        assert_eq!(part2(&tally), 35000);
+    }
+
+    #[test]
+    fn rejects_non_numbers() {
+        let err = parse("1000\n\n2000\nabc\n", TOP).unwrap_err();
+        assert_eq!((err.line, err.text.as_str()), (4, "abc"));
+        let tally = parse("1000\n\n\n2000\n", TOP).unwrap();
+        assert_eq!(tally.stats().elves, 2);
+        assert_eq!(tally.stats().median, 1500.0);
+        // the elf still being read counts too
+        let mut tally = Tally::new(1);
+        "1\n\n5\n\n3\n\n9\n\n4".lines().try_for_each(|line| tally.line(line)).unwrap();
+        assert_eq!(tally.stats().median, 4.0);
+        tally.line("2").unwrap();
+        assert_eq!(tally.stats().median, 5.0);
+    }
+
+    #[test]
+    fn top_option() {
+        let mut options = Options::default();
+        options.set("top", 4);
+        let tally = Day1::parse_with(&input::test_input(), &options).unwrap();
+        assert_eq!(part2(&tally), 51000);
+        // more than u32 holds
+        assert_eq!(part1(&parse("4000000000\n4000000000\n", TOP).unwrap()), 8_000_000_000);
+        options.set("top", 0);
+        assert!(Day1::parse_with("1000\n", &options).is_err());
+    }
+}
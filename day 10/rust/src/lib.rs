@@ -0,0 +1,205 @@
+//! Day 10: Cathode-Ray Tube
+
+use std::str::FromStr;
+use aoc_common::{animation::{Animate, Color, Frame}, parse::{self, ParseError}, solution::Solution};
+
+#[derive(Debug)]
+pub enum Instruction {
+    Addx(i32),
+    Noop
+}
+use Instruction::*;
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut split = s.split_whitespace();
+        if let Some(ins_str) = split.next() {
+            if ins_str=="addx" {
+                if let Some(val_str) = split.next() {
+                    let v: i32 = parse::field(s, val_str)?;
+                    return Ok(Addx(v));
+                }
+                return Err(ParseError::new(s, "", "expected integer after addx"));
+            } else if ins_str=="noop" {
+                return Ok(Noop);
+            }
+        }
+        Err(ParseError::new(s, s, "invalid instruction name, expected addx or noop"))
+    }
+}
+
+/// The handheld's CPU and CRT, which draws a pixel every cycle
+pub struct Emulator {
+    /// The X register, the middle of the 3 pixel wide sprite
+    pub register: i32,
+    /// The cycle in progress, counting from 1
+    pub cycle: usize,
+    /// Sum of signal strengths during the 20th, 60th, 100th... cycles
+    pub strength_sum: i32,
+    /// Pixels drawn so far, in rows of 40
+    pub screen: String,
+    debug: bool
+}
+
+impl Emulator {
+    
+    /// With `debug`, prints what happens each cycle
+    pub fn new(debug: bool) -> Self {
+        Emulator { register: 1, cycle: 1, strength_sum:0, screen: String::new(), debug }
+    }
+
+    fn crt(&mut self) {
+        let screen_pos: usize = (self.cycle-1)%40;
+
+        let c = if (self.register-1..=self.register+1).contains(&(screen_pos as i32)) 
+                       { '#' } else { '.' };
+
+        if self.debug {
+            println!("CRT prints {c} at {}", screen_pos);
+        }
+        self.screen.push(c);
+        if self.cycle.is_multiple_of(40) { self.screen.push('\n'); }
+    }
+
+    fn cycle(&mut self) {
+
+        // part 1
+        if self.cycle>=20 {
+            let offset_cycle = self.cycle - 20;
+            if offset_cycle.is_multiple_of(40) {
+                // dbg!((self.cycle as i32) * self.register);
+                self.strength_sum += (self.cycle as i32) * self.register;
+            }
+        }
+
+        // part 2
+        self.crt();
+
+        self.cycle += 1;
+    }
+
+    fn add(&mut self, v: i32) {
+        if self.debug { println!("begin executing add {v}"); }
+        self.cycle();
+        self.cycle();
+        self.register += v;
+        if self.debug { println!("finish executing add (Register is now {})", self.register); }
+    }
+
+    fn noop(&mut self) {
+        if self.debug { println!("begin executing noop"); }
+        self.cycle();
+    }
+
+    pub fn exec(&mut self, ins: &Instruction) {
+        match *ins {
+            Addx(v) => self.add(v),
+            Noop         => self.noop()
+        }
+    }
+
+    pub fn run(&mut self, prog: &[Instruction]) {
+        for ins in prog {
+            if self.debug { print!("Start cycle {}: ", self.cycle); }
+            self.exec(ins);
+            if self.debug { println!(); }
+        }
+    }
+
+}
+
+/// The CRT being drawn an instruction at a time, with the sprite below it
+pub struct CrtAnimation<'a> {
+    pub emu: Emulator,
+    prog: &'a [Instruction],
+    next: usize
+}
+
+impl<'a> CrtAnimation<'a> {
+    pub fn new(prog: &'a [Instruction]) -> Self {
+        CrtAnimation { emu: Emulator::new(false), prog, next: 0 }
+    }
+}
+
+impl Animate for CrtAnimation<'_> {
+    fn step(&mut self) -> bool {
+        let Some(ins) = self.prog.get(self.next) else { return false };
+        self.emu.exec(ins);
+        self.next += 1;
+        true
+    }
+
+    fn render(&self) -> Frame {
+        // 6 rows of 40 pixels, a gap, then the sprite
+        let mut frame = Frame::new(8, 40);
+        for (i, row) in self.emu.screen.lines().enumerate() {
+            for (j, c) in row.chars().enumerate() {
+                match c {
+                    '#' => frame.paint((i, j), '#', Color::Yellow),
+                    _ => frame.paint((i, j), '.', Color::DarkGrey)
+                }
+            }
+        }
+        for j in self.emu.register-1..=self.emu.register+1 {
+            if (0..40).contains(&j) {
+                frame.paint((7, j as usize), '#', Color::Green);
+            }
+        }
+        frame.caption = format!("cycle {}, X = {}", self.emu.cycle, self.emu.register);
+        frame
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse::lines(input)
+}
+
+/// Sum of the signal strengths during the 20th, 60th, 100th, 140th, 180th and 220th cycles
+pub fn part1(prog: &[Instruction]) -> i32 {
+    let mut emu = Emulator::new(false);
+    emu.run(prog);
+    emu.strength_sum
+}
+
+/// The letters drawn on the CRT
+pub fn part2(prog: &[Instruction]) -> String {
+    let mut emu = Emulator::new(false);
+    emu.run(prog);
+    emu.screen.trim_end().to_string()
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Parsed = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part1(prog: &Self::Parsed) -> String {
+        part1(prog).to_string()
+    }
+
+    fn part2(prog: &Self::Parsed) -> String {
+        part2(prog)
+    }
+
+    fn animations(prog: &Self::Parsed) -> Vec<Box<dyn Animate + '_>> {
+        vec![Box::new(CrtAnimation::new(prog))]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::examples;
+    use crate::*;
+
+    #[test]
+    fn readme_examples() {
+        examples::check::<Day10>();
+    }
+}
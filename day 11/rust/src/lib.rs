@@ -0,0 +1,272 @@
+//! Day 11: Monkey in the Middle
+
+use std::str::FromStr;
+use aoc_common::{
+    parse::{self, ParseError},
+    solution::Solution,
+};
+
+#[derive(Debug, Clone)]
+pub enum Operation {
+    Add,
+    Mult,
+}
+use Operation::*;
+
+/// A word of a monkey's operation, e.g. "old * 19"
+#[derive(Debug, Clone)]
+pub enum Token {
+    Const(u128),
+    Op(Operation),
+    Old,
+}
+
+impl FromStr for Token {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "old" {
+            Ok(Token::Old)
+        } else if s == "+" {
+            Ok(Token::Op(Add))
+        } else if s == "*" {
+            Ok(Token::Op(Mult))
+        } else if let Ok(n) = s.parse() {
+            Ok(Token::Const(n))
+        } else {
+            Err(ParseError::new(s, s, "expected old, +, * or a number"))
+        }
+    }
+}
+
+impl Token {
+    fn to_num(&self, old: u128) -> Result<u128, ()> {
+        match *self {
+            Token::Old => Ok(old),
+            Token::Const(c) => Ok(c),
+            Token::Op(_) => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    _number: u128,
+    /// Worry levels of the items held
+    pub inventory: Vec<u128>,
+    /// Operand, operator, operand
+    pub expr: Vec<Token>,
+    pub div_by: u128,
+    /// Monkey thrown to when the worry level is divisible by `div_by`
+    pub pass_throw: u128,
+    pub fail_throw: u128,
+    /// Number of items inspected so far
+    pub inspected: usize,
+}
+
+impl FromStr for Monkey {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().map(|line| line.trim_start());
+        // each field is on its own line, after a fixed prefix
+        let mut field = |prefix: &str| match lines.next() {
+            Some(line) => parse::after(line, prefix).map_err(|err| err.within(s, line)),
+            None => Err(ParseError::new(s, "", format!("expected line starting {prefix:?}"))),
+        };
+
+        let number_str = field("Monkey ")?;
+        let _number: u128 = match number_str.strip_suffix(':') {
+            Some(n) => parse::field(s, n)?,
+            None => return Err(ParseError::new(s, number_str, "expected ':' after monkey number")),
+        };
+
+        let inventory: Vec<u128> = field("Starting items: ")?
+            .split(", ")
+            .map(|item| parse::field(s, item))
+            .collect::<Result<_, _>>()?;
+
+        let expr_str = field("Operation: new = ")?;
+        let expr: Vec<Token> = expr_str
+            .split(' ')
+            .map(|token| token.parse().map_err(|err: ParseError| err.within(s, token)))
+            .collect::<Result<_, _>>()?;
+        if !matches!(expr[..], [ref a, Token::Op(_), ref b] if a.to_num(0).is_ok() && b.to_num(0).is_ok()) {
+            return Err(ParseError::new(s, expr_str, "expected \"<operand> <+ or *> <operand>\""));
+        }
+
+        let div_by: u128 = parse::field(s, field("Test: divisible by ")?)?;
+        let pass_throw: u128 = parse::field(s, field("If true: throw to monkey ")?)?;
+        let fail_throw: u128 = parse::field(s, field("If false: throw to monkey ")?)?;
+
+        Ok(Monkey {
+            _number,
+            inventory,
+            expr,
+            div_by,
+            pass_throw,
+            fail_throw,
+            inspected: 0,
+        })
+    }
+}
+
+impl Monkey {
+    /// The new worry level of an item after inspection
+    pub fn op(&self, old: u128) -> u128 {
+        let a = self.expr[0].to_num(old).unwrap();
+        let b = self.expr[2].to_num(old).unwrap();
+        match self.expr[1] {
+            Token::Op(Add) => a + b,
+            Token::Op(Mult) => a * b,
+            _ => panic!("unexpected"),
+        }
+    }
+
+    /// Worry level and destination of each item thrown. Worry levels are divided by 3 after
+    /// inspection, or without relief taken modulo `p`.
+    pub fn turn(&mut self, p: Option<u128>) -> Vec<(u128, u128)> {
+        let mut throw_list = vec![];
+        for &item_wl in self.inventory.iter() {
+            // println!("Monkey inspects an item with worry level of {}", item_wl);
+            let mut new_wl = self.op(item_wl);
+            // println!("Worry level changes to {}", new_wl);
+            if let Some(prod) = p {
+                new_wl %= prod
+            } else {
+                new_wl /= 3;
+            }
+            // println!("Monkey gets bored, new worry level is {}", new_wl);
+            let test = new_wl.is_multiple_of(self.div_by);
+            // println!(
+            //     "Current worry level {} divisible by {}",
+            //     if test { "is" } else { "is not" },
+            //     self.div_by
+            // );
+            let thrown_to = if test {
+                self.pass_throw
+            } else {
+                self.fail_throw
+            };
+            // println!(
+            //     "Item with worry level {} thrown to monkey {}",
+            //     new_wl, thrown_to
+            // );
+            throw_list.push((new_wl, thrown_to))
+        }
+        throw_list
+    }
+}
+
+pub struct Sim {
+    pub monkeys: Vec<Monkey>,
+}
+
+impl Sim {
+    /// Each monkey takes a turn in order
+    pub fn round(&mut self, p: Option<u128>) {
+        for i in 0..self.monkeys.len() {
+            let throw_list = self.monkeys[i].turn(p);
+            self.monkeys[i].inspected += throw_list.len();
+            self.monkeys[i].inventory.clear();
+            for (new_wl, thrown_to) in throw_list {
+                self.monkeys[thrown_to as usize].inventory.push(new_wl);
+            }
+        }
+    }
+
+    /// Level of monkey business after `rounds`: the product of the two highest inspection counts
+    pub fn run(&mut self, p: Option<u128>, rounds: usize) -> usize {
+        for _ in 0..rounds {
+            self.round(p);
+        }
+
+        let mut activity: Vec<usize> = self.monkeys.iter().map(|m| m.inspected).collect();
+        // dbg!(&activity);
+        activity.sort();
+        activity.reverse();
+
+        activity[0] * activity[1]
+    }
+}
+
+/// Each monkey in turn, having checked every monkey they throw to exists
+pub fn parse(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let monkeys: Vec<Monkey> = parse::blocks(input)?;
+    for (block, monkey) in input.split("\n\n").zip(&monkeys) {
+        let targets = [
+            ("If true: throw to monkey ", monkey.pass_throw),
+            ("If false: throw to monkey ", monkey.fail_throw),
+        ];
+        for (prefix, target) in targets {
+            if target >= monkeys.len() as u128 {
+                let line = block.lines().map(str::trim_start).find(|line| line.starts_with(prefix));
+                let text = line.map_or("", |line| &line[prefix.len()..]);
+                let reason = format!("there are only monkeys 0 to {}", monkeys.len() - 1);
+                return Err(ParseError::new(input, text, reason));
+            }
+        }
+    }
+    Ok(monkeys)
+}
+
+/// Monkey business after 20 rounds, with worry levels divided by 3 after each inspection
+pub fn part1(monkeys: &[Monkey]) -> usize {
+    let mut sim = Sim {
+        monkeys: monkeys.to_vec(),
+    };
+
+    sim.run(None, 20)
+}
+
+/// Monkey business after 10000 rounds without relief
+pub fn part2(monkeys: &[Monkey]) -> usize {
+    let mut sim2 = Sim {
+        monkeys: monkeys.to_vec(),
+    };
+
+    // every test still holds modulo the product of the divisors
+    let prod = sim2.monkeys.iter().map(|m| m.div_by).product();
+    sim2.run(Some(prod), 10_000)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Parsed = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part1(monkeys: &Self::Parsed) -> String {
+        part1(monkeys).to_string()
+    }
+
+    fn part2(monkeys: &Self::Parsed) -> String {
+        part2(monkeys).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input;
+    use crate::*;
+
+    #[test]
+    fn bad_prefix() {
+        let contents = input::test_input().replacen("Test: divisible", "Test: divisable", 1);
+        let err = parse(&contents).unwrap_err();
+        assert_eq!((err.line, err.column), (4, 3));
+    }
+
+    #[test]
+    fn no_such_target() {
+        let contents = input::test_input()
+            .replacen("If false: throw to monkey 3", "If false: throw to monkey 7", 1);
+        let err = parse(&contents).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (6, 31, "7"));
+        assert_eq!(err.reason, "there are only monkeys 0 to 3");
+    }
+}
@@ -0,0 +1,284 @@
+//! Day 12: Hill Climbing Algorithm
+
+use aoc_common::{
+    animation::{Animate, Color, Frame},
+    graph::{self, Cost, Paths},
+    grid::{Grid2D, Pos},
+    parse::ParseError,
+    solution::Solution,
+};
+use std::fmt::Display;
+
+#[derive(Debug, Clone)]
+pub enum Space {
+    Start,
+    End,
+    Mid(char),
+}
+
+impl Space {
+    /// Height as a letter, a lowest to z highest
+    pub fn elevation(&self) -> u8 {
+        match *self {
+            Space::Start => b'a',
+            Space::End => b'z',
+            Space::Mid(c) => c as u8,
+        }
+    }
+
+    /// Climbing at most one step up, or down any distance
+    pub fn can_reach(&self, dest: &Space) -> bool {
+        dest.elevation() <= self.elevation() + 1
+    }
+}
+
+#[derive(Clone)]
+pub struct HeightMap {
+    pub spaces: Grid2D<Space>,
+    pub start: Pos,
+    pub end: Pos,
+}
+
+/// Positions in order, each a step from the last
+#[derive(Clone)]
+pub struct Path {
+    pub spaces: Vec<Pos>,
+}
+
+impl Display for Path {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut string = String::new();
+        for (i, j) in &self.spaces {
+            string += format!("({}, {})  ", i, j).as_str();
+        }
+        write!(f, "{}", string)?;
+        Ok(())
+    }
+}
+
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+fn dir((fi, fj): Pos, (ti, tj): Pos) -> Direction {
+    if ti == fi + 1 {
+        Direction::Down
+    } else if ti + 1 == fi {
+        Direction::Up
+    } else if tj == fj + 1 {
+        Direction::Right
+    } else if tj + 1 == fj {
+        Direction::Left
+    } else {
+        panic!("Unexpected jump");
+    }
+}
+
+fn char_dir(from: Pos, to: Pos) -> char {
+    match dir(from, to) {
+        Direction::Left => '<',
+        Direction::Right => '>',
+        Direction::Up => '^',
+        Direction::Down => 'v',
+    }
+}
+
+impl HeightMap {
+    pub fn parse(contents: &str) -> Result<Self, ParseError> {
+        let mut start = None;
+        let mut end = None;
+        let spaces = Grid2D::parse(contents, |pos, c| {
+            if c == 'S' {
+                start = Some(pos);
+                Ok(Space::Start)
+            } else if c == 'E' {
+                end = Some(pos);
+                Ok(Space::End)
+            } else if c.is_ascii_lowercase() {
+                Ok(Space::Mid(c))
+            } else {
+                Err("expected S, E or a height a-z")
+            }
+        })?;
+        let start = start.ok_or_else(|| ParseError::new(contents, "", "no start S"))?;
+        let end = end.ok_or_else(|| ParseError::new(contents, "", "no end E"))?;
+
+        Ok(HeightMap { spaces, start, end })
+    }
+
+    /// Steps to every space reachable from any of `starts`, climbing as in `Space::can_reach`
+    fn floodfill(&self, starts: impl IntoIterator<Item = Pos>) -> Paths<Pos> {
+        let moves = graph::implicit(|pos| {
+            self.spaces
+                .neighbours4(pos)
+                .filter(move |&next| self.spaces[pos].can_reach(&self.spaces[next]))
+                .map(|next| (next, 1))
+        });
+        graph::bfs(&moves, starts)
+    }
+
+    /// None if the fill never reached `pos`
+    fn path_to(fill: &Paths<Pos>, pos: Pos) -> Option<Path> {
+        fill.path_to(pos).map(|spaces| Path { spaces })
+    }
+}
+
+/// A flood fill spreading a step at a time, then the shortest path it found
+pub struct FillAnimation<'a> {
+    pub grid: &'a HeightMap,
+    pub fill: Paths<Pos>,
+    /// None if the end can't be reached
+    pub shortest: Option<Path>,
+    /// Spaces up to this many steps from the start are drawn
+    pub reached: Cost,
+}
+
+impl<'a> FillAnimation<'a> {
+    pub fn new(grid: &'a HeightMap, (fill, shortest): (Paths<Pos>, Option<Path>)) -> Self {
+        FillAnimation {
+            grid,
+            fill,
+            shortest,
+            reached: 0,
+        }
+    }
+
+    fn furthest(&self) -> Cost {
+        self.fill.dist.values().copied().max().unwrap_or(0)
+    }
+}
+
+impl Animate for FillAnimation<'_> {
+    fn step(&mut self) -> bool {
+        if self.reached > self.furthest() {
+            return false;
+        }
+        self.reached += 1;
+        true
+    }
+
+    /// Each space reached shown by its elevation, green near the start turning blue with
+    /// distance, and the path drawn over them once the fill is complete
+    fn render(&self) -> Frame {
+        let spaces = &self.grid.spaces;
+        let mut frame = Frame::new(spaces.height(), spaces.width());
+        for (pos, space) in spaces.iter() {
+            match self.fill.distance(pos) {
+                Some(pd) if pd <= self.reached => {
+                    let co = (pd / 3).min(255) as u8;
+                    let colour = Color::Rgb {
+                        r: 0,
+                        g: 255 - co,
+                        b: co,
+                    };
+                    frame.paint(pos, space.elevation() as char, colour);
+                }
+                _ => frame.set(pos, '.'),
+            }
+        }
+        let shortest = self.shortest.as_ref();
+        if self.reached > self.furthest() {
+            for step in shortest.into_iter().flat_map(|path| path.spaces.windows(2)) {
+                frame.paint(step[0], char_dir(step[0], step[1]), Color::White);
+            }
+        }
+        let reached = self.reached.min(self.furthest());
+        frame.caption = match shortest {
+            Some(path) => format!(
+                "{reached} steps reached, shortest path {}",
+                path.spaces.len() - 1
+            ),
+            None => format!("{reached} steps reached, no route to the end"),
+        };
+        frame
+    }
+}
+
+/// Shortest path from the start to the end, if there is one, and the fill from the start that
+/// looked for it
+pub fn climb(grid: &HeightMap) -> (Paths<Pos>, Option<Path>) {
+    let fill = grid.floodfill([grid.start]);
+    let shortest = HeightMap::path_to(&fill, grid.end);
+    (fill, shortest)
+}
+
+/// Shortest path from any lowest space to the end, if there is one, and the fill from all of them
+/// that looked for it
+pub fn descend(grid: &HeightMap) -> (Paths<Pos>, Option<Path>) {
+    let lowest = grid
+        .spaces
+        .iter()
+        .filter(|(_, space)| space.elevation() == b'a')
+        .map(|(pos, _)| pos);
+    let fill = grid.floodfill(lowest);
+    let shortest = HeightMap::path_to(&fill, grid.end);
+    (fill, shortest)
+}
+
+pub fn parse(input: &str) -> Result<HeightMap, ParseError> {
+    HeightMap::parse(input)
+}
+
+/// Fewest steps from the start to the end, or None if it can't be reached
+pub fn part1(grid: &HeightMap) -> Option<usize> {
+    let (_, shortest) = climb(grid);
+    shortest.map(|path| path.spaces.len() - 1)
+}
+
+/// Fewest steps from any lowest space to the end, or None if it can't be reached
+pub fn part2(grid: &HeightMap) -> Option<usize> {
+    let (_, shortest) = descend(grid);
+    shortest.map(|path| path.spaces.len() - 1)
+}
+
+fn answer(steps: Option<usize>) -> String {
+    steps.map_or("no route".to_string(), |steps| steps.to_string())
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Parsed = HeightMap;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part1(grid: &Self::Parsed) -> String {
+        answer(part1(grid))
+    }
+
+    fn part2(grid: &Self::Parsed) -> String {
+        answer(part2(grid))
+    }
+
+    fn animations(grid: &Self::Parsed) -> Vec<Box<dyn Animate + '_>> {
+        vec![
+            Box::new(FillAnimation::new(grid, climb(grid))),
+            Box::new(FillAnimation::new(grid, descend(grid))),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_route() {
+        // E is at z, far above the a and b around it
+        let grid = parse("Sab\nbaE\n").unwrap();
+        assert_eq!(part1(&grid), None);
+        assert_eq!(Day12::part2(&grid), "no route");
+        let mut fill = FillAnimation::new(&grid, climb(&grid));
+        while fill.step() {}
+        assert!(fill.render().caption.ends_with("no route to the end"));
+
+        let grid = parse("SbcdefghijklmnopqrstuvwxyE\n").unwrap();
+        assert_eq!(part1(&grid), Some(25));
+    }
+}
@@ -0,0 +1,185 @@
+//! Day 13: Distress Signal
+
+// very much like https://fasterthanli.me/series/advent-of-code-2022/part-13
+
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use aoc_common::{parse::ParseError, solution::Solution};
+
+/// A packet, or a value or list within one. Ordered as the puzzle describes.
+#[derive(PartialEq, Eq)]
+pub enum Node {
+    Sublist(Vec<Node>),
+    Value(u64),
+}
+
+impl Clone for Node {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Sublist(sl) => Self::Sublist(Vec::from_iter(sl.iter().cloned())),
+            Self::Value(v) => Self::Value(*v),
+        }
+    }
+}
+
+impl Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Value(n) => write!(f, "{n}"),
+            Self::Sublist(sl) => {
+                let mut string = String::new();
+                for val in sl {
+                    string += format!("{} ", val).as_str();
+                }
+                write!(f, "[ {}]", string)
+            }
+        }
+    }
+}
+
+impl TryFrom<serde_json::Value> for Node {
+    type Error = String;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        match value {
+            serde_json::Value::Number(n) => n
+                .as_u64()
+                .map(Node::Value)
+                .ok_or_else(|| format!("{n} is not a non-negative integer")),
+            serde_json::Value::Array(vec) => Ok(Node::Sublist(
+                vec.into_iter()
+                    .map(Node::try_from)
+                    .collect::<Result<Vec<_>, _>>()?,
+            )),
+            other => Err(format!("{other} is not an integer or list")),
+        }
+    }
+}
+
+impl FromStr for Node {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match serde_json::from_str::<serde_json::Value>(s) {
+            Ok(v) => Node::try_from(v).map_err(|err| ParseError::new(s, s, err)),
+            Err(err) => {
+                // serde's message ends with its own " at line 1 column N"
+                let reason = err.to_string();
+                let reason = reason.split(" at line ").next().unwrap_or_default();
+                let at = s.get(err.column().saturating_sub(1)..).unwrap_or_default();
+                Err(ParseError::new(s, at, reason))
+            }
+        }
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Node::Value(n), Node::Value(no)) => n.cmp(no),
+            (Node::Sublist(sl), Node::Sublist(slo)) => sl
+                .iter()
+                .zip(slo.iter())
+                .map(|(n, no)| n.cmp(no))
+                .find(|ord| *ord != Ordering::Equal)
+                .unwrap_or_else(|| sl.len().cmp(&slo.len())),
+            (Node::Value(n), sl) => {
+                let new_node = Node::Sublist(vec![Node::Value(*n)]);
+                new_node.cmp(sl)
+            }
+            (sl, Node::Value(n)) => {
+                let new_node = Node::Sublist(vec![Node::Value(*n)]);
+                sl.cmp(&new_node)
+            }
+        }
+    }
+}
+
+// derived partial ordering can be wrong?
+// https://github.com/rust-lang/rust-clippy/issues/1621
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        // // debug
+        // let cmp = match self.cmp(other) {
+        //     Ordering::Less => "<",
+        //     Ordering::Equal => "=",
+        //     Ordering::Greater => ">",
+        // };
+        // println!("{} {} {}", self, cmp, other);
+
+        Some(self.cmp(other))
+    }
+}
+
+/// Every packet in order, ignoring the blank lines between pairs
+pub fn parse(input: &str) -> Result<Vec<Node>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| {
+            l.trim()
+                .parse::<Node>()
+                .map_err(|err| err.within(l, l.trim()).offset_lines(i))
+        })
+        .collect()
+}
+
+/// Sum of the (1 based) indices of pairs already in the right order
+pub fn part1(packets: &[Node]) -> usize {
+    packets
+        .chunks(2)
+        .enumerate()
+        .fold(0, |s, (i, packet_slice)| {
+            if packet_slice[0] < packet_slice[1] {
+                s + i + 1
+            } else {
+                s
+            }
+        })
+}
+
+/// Product of the positions of the divider packets once all packets are sorted
+pub fn part2(packets: &[Node]) -> usize {
+    let mut packets = packets.to_vec();
+    let divider_1 = "[[2]]".parse::<Node>().unwrap();
+    let divider_2 = "[[6]]".parse::<Node>().unwrap();
+    packets.push(divider_1.clone());
+    packets.push(divider_2.clone());
+    packets.sort();
+    let (i1, _) = packets
+        .iter()
+        .enumerate()
+        .find(|(_, n)| **n == divider_1)
+        .unwrap();
+    let (i2, _) = packets
+        .iter()
+        .enumerate()
+        .find(|(_, n)| **n == divider_2)
+        .unwrap();
+
+    (i1 + 1) * (i2 + 1)
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Parsed = Vec<Node>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part1(packets: &Self::Parsed) -> String {
+        part1(packets).to_string()
+    }
+
+    fn part2(packets: &Self::Parsed) -> String {
+        part2(packets).to_string()
+    }
+}
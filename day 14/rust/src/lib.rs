@@ -0,0 +1,324 @@
+//! Day 14: Regolith Reservoir
+
+use aoc_common::{
+    animation::{self, Animate, Color, Frame},
+    grid::{Grid2D, Pos},
+    parse::{self, ParseError},
+    point::Point,
+    solution::Solution,
+};
+use std::str::FromStr;
+
+/// A line of rock through each point in turn, written "x,y -> x,y -> ..."
+#[derive(Debug)]
+pub struct Path {
+    pub points: Vec<Point>,
+}
+
+impl FromStr for Path {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let points = s
+            .split(" -> ")
+            .map(|ps| ps.parse().map_err(|err: ParseError| err.within(s, ps)))
+            .collect::<Result<Vec<Point>, _>>()?;
+        Ok(Path { points })
+    }
+}
+
+impl From<Vec<Point>> for Path {
+    fn from(points: Vec<Point>) -> Self {
+        Path { points }
+    }
+}
+
+struct LineSegmentIterator {
+    increment_x: bool,
+    coord_max: i32,
+    current: Point,
+}
+
+impl Iterator for LineSegmentIterator {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let curr = self.current; // inclusive, so endpoints will overlap
+        let cont = match self.increment_x {
+            true => {
+                self.current.x += 1;
+                self.current.x <= self.coord_max + 1
+            }
+            false => {
+                self.current.y += 1;
+                self.current.y <= self.coord_max + 1
+            }
+        };
+
+        cont.then_some(curr)
+    }
+}
+
+impl LineSegmentIterator {
+    fn new(start: Point, end: Point) -> Self {
+        let increment_x = start.x != end.x;
+        let (coord_max, current) = match increment_x {
+            true => match start.x <= end.x {
+                true => (end.x, start),
+                false => (start.x, end),
+            },
+            false => match start.y <= end.y {
+                true => (end.y, start),
+                false => (start.y, end),
+            },
+        };
+        LineSegmentIterator {
+            increment_x,
+            coord_max,
+            current,
+        }
+    }
+}
+
+impl Path {
+    /// bounding box bottom left
+    fn bb_tl(&self, extra: Point) -> Point {
+        self.points.iter().fold(extra, |acc, p| acc.bb_tl(*p))
+    }
+    /// bounding box top right
+    fn bb_br(&self, extra: Point) -> Point {
+        self.points.iter().fold(extra, |acc, p| acc.bb_br(*p))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GridSpace {
+    Rock,
+    Air,
+    Sand,
+}
+
+/// The scanned rock, with sand added as it comes to rest. Sand pours in from 500,0.
+pub struct Cave {
+    /// Every space sand can reach, down to just above the floor
+    pub spaces: Grid2D<GridSpace>,
+    /// The point at the grid's top left
+    pub origin: Point,
+    /// y of the lowest rock
+    pub depth: i32,
+}
+
+const SOURCE: Point = Point { x: 500, y: 0 };
+
+impl Cave {
+    pub fn from_walls(walls: &[Path]) -> Cave {
+        let tl = Path::from(
+            walls
+                .iter()
+                .map(|wall| wall.bb_tl(SOURCE))
+                .collect::<Vec<Point>>(),
+        )
+        .bb_tl(SOURCE);
+        let br = Path::from(
+            walls
+                .iter()
+                .map(|wall| wall.bb_br(SOURCE))
+                .collect::<Vec<Point>>(),
+        )
+        .bb_br(SOURCE);
+
+        // sand moves at most one space sideways for each space it falls, and no lower than the
+        // floor at depth + 2
+        let floor = br.y + 2;
+        let origin = tl.bb_tl(Point::new(SOURCE.x - floor, SOURCE.y));
+        let br = br.bb_br(Point::new(SOURCE.x + floor, floor - 1));
+        let mut spaces = Grid2D::filled(
+            (br.y - origin.y + 1) as usize,
+            (br.x - origin.x + 1) as usize,
+            GridSpace::Air,
+        );
+
+        for wall in walls {
+            for i in 0..wall.points.len() - 1 {
+                // overlapping endpoints set twice for brevity
+                for point in LineSegmentIterator::new(wall.points[i], wall.points[i + 1]) {
+                    spaces[Cave::pos(origin, point)] = GridSpace::Rock;
+                }
+            }
+        }
+
+        Cave {
+            spaces,
+            origin,
+            depth: br.y - 1,
+        }
+    }
+
+    fn pos(origin: Point, point: Point) -> Pos {
+        ((point.y - origin.y) as usize, (point.x - origin.x) as usize)
+    }
+
+    fn get(&self, point: Point) -> GridSpace {
+        self.spaces[Cave::pos(self.origin, point)]
+    }
+
+    fn available(&self, pos: Point, part2: bool) -> bool {
+        if part2 && pos.y == self.depth + 2 {
+            return false;
+        }
+        self.get(pos) == GridSpace::Air
+    }
+
+    fn next_pos(&self, previous_pos: Point, part2: bool) -> Option<Point> {
+        let down = Point::new(previous_pos.x, previous_pos.y + 1);
+        if self.available(down, part2) {
+            return Some(down);
+        }
+        let left = Point::new(previous_pos.x - 1, previous_pos.y + 1);
+        if self.available(left, part2) {
+            return Some(left);
+        }
+        let right = Point::new(previous_pos.x + 1, previous_pos.y + 1);
+        if self.available(right, part2) {
+            return Some(right);
+        }
+        None
+    }
+
+    /// Whether a grain came to rest. With `part2` there is a floor 2 below the lowest rock,
+    /// otherwise grains can fall into the abyss.
+    pub fn drop_grain(&mut self, part2: bool) -> bool {
+        self.settle_grain(part2).is_some()
+    }
+
+    /// Where a grain came to rest, if it did
+    fn settle_grain(&mut self, part2: bool) -> Option<Point> {
+        if self.get(SOURCE) != GridSpace::Air {
+            return None;
+        }
+        let mut grain_pos = SOURCE;
+
+        while let Some(new_pos) = self.next_pos(grain_pos, part2) {
+            grain_pos = new_pos;
+            if !part2 && grain_pos.y >= self.depth {
+                return None;
+            }
+        }
+
+        self.spaces[Cave::pos(self.origin, grain_pos)] = GridSpace::Sand;
+        Some(grain_pos)
+    }
+
+    /// Number of grains which come to rest
+    pub fn part(&mut self, part2: bool) -> usize {
+        let mut i = 0;
+        while self.drop_grain(part2) {
+            i += 1;
+        }
+        i
+    }
+}
+
+/// Sand filling the cave a grain at a time
+pub struct SandAnimation {
+    pub cave: Cave,
+    part2: bool,
+    grains: usize,
+    last: Option<Point>,
+}
+
+impl SandAnimation {
+    pub fn new(walls: &[Path], part2: bool) -> Self {
+        SandAnimation {
+            cave: Cave::from_walls(walls),
+            part2,
+            grains: 0,
+            last: None,
+        }
+    }
+}
+
+impl Animate for SandAnimation {
+    fn step(&mut self) -> bool {
+        self.last = self.cave.settle_grain(self.part2);
+        self.grains += self.last.is_some() as usize;
+        self.last.is_some()
+    }
+
+    /// The bounding box of the rock and sand, following the latest grain
+    fn render(&self) -> Frame {
+        let spaces = &self.cave.spaces;
+        let occupied: Vec<Pos> = spaces
+            .iter()
+            .filter(|(_, gs)| **gs != GridSpace::Air)
+            .map(|(pos, _)| pos)
+            .chain([Cave::pos(self.cave.origin, SOURCE)])
+            .collect();
+        let left = occupied.iter().map(|&(_, j)| j).min().unwrap();
+        let right = occupied.iter().map(|&(_, j)| j).max().unwrap();
+        let top = occupied.iter().map(|&(i, _)| i).min().unwrap();
+        let bottom = occupied.iter().map(|&(i, _)| i).max().unwrap();
+
+        let mut frame = Frame::new(bottom - top + 1, right - left + 1);
+        for i in top..=bottom {
+            for (j, gs) in spaces.row(i)[left..=right].iter().enumerate() {
+                match gs {
+                    GridSpace::Air => frame.set((i - top, j), '.'),
+                    GridSpace::Rock => frame.paint((i - top, j), '#', Color::DarkGrey),
+                    GridSpace::Sand => frame.paint((i - top, j), 'o', Color::Yellow),
+                }
+            }
+        }
+        if let Some(grain) = self.last {
+            let (i, j) = Cave::pos(self.cave.origin, grain);
+            frame.focus = Some((i - top, j - left));
+        }
+        frame.caption = format!("{} grains at rest", self.grains);
+        frame
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Path>, ParseError> {
+    parse::lines(input)
+}
+
+/// Units of sand that come to rest before sand starts falling into the abyss
+pub fn part1(walls: &[Path]) -> usize {
+    Cave::from_walls(walls).part(false)
+}
+
+/// Units of sand that come to rest on the floor before the source is blocked
+pub fn part2(walls: &[Path]) -> usize {
+    Cave::from_walls(walls).part(true)
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Parsed = Vec<Path>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part1(walls: &Self::Parsed) -> String {
+        part1(walls).to_string()
+    }
+
+    fn part2(walls: &Self::Parsed) -> String {
+        part2(walls).to_string()
+    }
+
+    fn animations(walls: &Self::Parsed) -> Vec<Box<dyn Animate + '_>> {
+        vec![
+            Box::new(SandAnimation::new(walls, false)),
+            Box::new(SandAnimation::new(walls, true)),
+        ]
+    }
+
+    /// Only part 1, as part 2 takes tens of thousands of grains
+    fn visualise(walls: &Self::Parsed) {
+        animation::play(&mut SandAnimation::new(walls, false));
+    }
+}
@@ -0,0 +1,132 @@
+//! Day 15: Beacon Exclusion Zone
+
+use aoc_common::{
+    interval::{Interval, IntervalSet},
+    parse::{self, ParseError},
+    point::Point,
+    solution::Solution,
+};
+use regex::Regex;
+use std::collections::HashSet;
+
+#[macro_use]
+extern crate lazy_static;
+
+#[derive(Debug)]
+pub struct SensorData {
+    pub sensor_pos: Point,
+    /// nearest detected beacon, multiple sensors can point to it
+    pub beacon_pos: Point,
+    /// Manhattan distance to the beacon, within which there are no other beacons
+    pub empty_zone_radius: i32,
+}
+
+pub fn parse(contents: &str) -> Result<Vec<SensorData>, ParseError> {
+    lazy_static! {
+        static ref REG: Regex =
+            Regex::new(r"Sensor at x=(.+), y=(.+): closest beacon is at x=(.+), y=(.+)")
+                .expect("Regex compile failure");
+    }
+
+    let mut data: Vec<SensorData> = vec![];
+
+    for line in contents.lines() {
+        if let Some(cap) = REG.captures(line) {
+            let coord = |i| parse::field(contents, cap.get(i).unwrap().as_str());
+            let sensor_pos = Point {
+                x: coord(1)?,
+                y: coord(2)?,
+            };
+            let beacon_pos = Point {
+                x: coord(3)?,
+                y: coord(4)?,
+            };
+            data.push(SensorData {
+                sensor_pos,
+                beacon_pos,
+                empty_zone_radius: sensor_pos.dist_to(beacon_pos),
+            });
+        } else if !line.is_empty() {
+            return Err(ParseError::new(
+                contents,
+                line,
+                "expected \"Sensor at x=<x>, y=<y>: closest beacon is at x=<x>, y=<y>\"",
+            ));
+        }
+    }
+
+    Ok(data)
+}
+
+/// Positions in `row` within range of each sensor, as close as its beacon
+pub fn row_coverage(data: &[SensorData], row: i32) -> IntervalSet<i32> {
+    data.iter()
+        .map(|d| {
+            // distance to sensor projected to row, empty if out of range
+            let remaining_dist = d.empty_zone_radius - (row - d.sensor_pos.y).abs();
+            Interval::new(d.sensor_pos.x - remaining_dist, d.sensor_pos.x + remaining_dist)
+        })
+        .collect()
+}
+
+/// Positions in `row` where a beacon can't be
+pub fn no_beacon_positions(data: &[SensorData], row: i32) -> usize {
+    let covered = row_coverage(data, row);
+    let beacons_in_row: HashSet<i32> = data
+        .iter()
+        .filter(|d| d.beacon_pos.y == row && covered.contains(d.beacon_pos.x))
+        .map(|d| d.beacon_pos.x)
+        .collect();
+    covered.len() as usize - beacons_in_row.len()
+}
+
+/// Tuning frequency of the only position, with coordinates 0 to `max_coord`, where the distress beacon can be
+pub fn tuning_frequency(data: &[SensorData], max_coord: i32) -> i128 {
+    let area = Interval::new(0, max_coord);
+    for y in 0..=max_coord {
+        if let Some(gap) = row_coverage(data, y).gaps(area).intervals().first() {
+            return (gap.low as i128) * 4_000_000 + (y as i128);
+        }
+    }
+    0
+}
+
+/// The example differs from real inputs in which row is checked (part 1) and the search area (part 2),
+/// so (row, max_coord) are picked by the scale of the sensor positions
+pub fn puzzle_params(data: &[SensorData]) -> (i32, i32) {
+    let is_example = data.iter().all(|d| d.sensor_pos.x.abs() < 1000 && d.sensor_pos.y.abs() < 1000);
+    if is_example {
+        (10, 20)
+    } else {
+        (2_000_000, 4_000_000)
+    }
+}
+
+pub fn part1(data: &[SensorData]) -> usize {
+    let (row, _) = puzzle_params(data);
+    no_beacon_positions(data, row)
+}
+
+pub fn part2(data: &[SensorData]) -> i128 {
+    let (_, max_coord) = puzzle_params(data);
+    tuning_frequency(data, max_coord)
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Parsed = Vec<SensorData>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part1(data: &Self::Parsed) -> String {
+        part1(data).to_string()
+    }
+
+    fn part2(data: &Self::Parsed) -> String {
+        part2(data).to_string()
+    }
+}
@@ -0,0 +1,517 @@
+//! Day 16: Proboscidea Volcanium
+
+use aoc_common::{
+    animation::{Animate, Color, Frame},
+    graph::{self, AdjacencyMap, Paths},
+    parse::{self, ParseError},
+    solution::Solution,
+};
+use crossterm::{cursor, ExecutableCommand};
+use regex::Regex;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    io::stderr,
+    str::FromStr,
+    time::Instant,
+};
+
+
+extern crate rayon;
+
+use rayon::prelude::*;
+
+#[macro_use]
+extern crate lazy_static;
+
+#[derive(Debug)]
+pub struct Valve {
+    pub name: String,
+    /// Pressure released per minute once opened
+    pub rate: u32,
+    /// Indices of the valves reachable through a tunnel
+    pub connections: Vec<usize>,
+}
+
+fn parse_valve(line: &str) -> Result<(&str, u32, Vec<&str>), ParseError> {
+    lazy_static! {
+        static ref REG: Regex = Regex::new(
+            r"Valve (.+) has flow rate=(.+); tunnels{0,1} leads{0,1} to valves{0,1} (.+)$"
+        )
+        .expect("Regex compile failure");
+    }
+
+    let name: &str;
+    let rate: u32;
+    let conn: &str;
+    if let Some(cap) = REG.captures(line) {
+        name = cap.get(1).unwrap().as_str();
+        rate = parse::field(line, cap.get(2).unwrap().as_str())?;
+        conn = cap.get(3).unwrap().as_str();
+    } else {
+        return Err(ParseError::new(
+            line,
+            line,
+            "expected \"Valve <name> has flow rate=<rate>; tunnels lead to valves <names>\"",
+        ));
+    }
+
+    Ok((name, rate, conn.split(", ").collect()))
+}
+
+/// The valves, indexed by their position in the input, and the shortest paths between them
+pub struct ValveNetwork {
+    pub all: Vec<Valve>,
+    /// Indices of the valves with a non-zero flow rate
+    pub major: Vec<usize>,
+    /// Shortest paths from the start and from each major valve
+    paths: HashMap<usize, Paths<usize>>,
+    num: usize,
+    /// Index of valve AA
+    pub start_idx: usize,
+}
+
+impl FromStr for ValveNetwork {
+    type Err = ParseError;
+
+    fn from_str(contents: &str) -> Result<Self, Self::Err> {
+        let parsed_valves = contents
+            .lines()
+            .enumerate()
+            .map(|(i, line)| parse_valve(line).map_err(|err| err.offset_lines(i)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut all = vec![];
+        let mut major = vec![];
+        let num = parsed_valves.len();
+
+        let mut ids = HashMap::new();
+
+        for (i, (name, rate, _)) in parsed_valves.iter().enumerate() {
+            ids.insert(*name, i);
+            if *rate > 0 {
+                major.push(i);
+            }
+        }
+
+        let start_idx = *ids
+            .get("AA")
+            .ok_or_else(|| ParseError::new(contents, "", "no starting valve AA"))?;
+
+        for (name, rate, str_conn) in parsed_valves.iter() {
+            let connections = str_conn
+                .iter()
+                .map(|to| {
+                    ids.get(to)
+                        .copied()
+                        .ok_or_else(|| ParseError::new(contents, to, "tunnel leads to unknown valve"))
+                })
+                .collect::<Result<Vec<usize>, _>>()?;
+            all.push(Valve {
+                name: name.to_string(),
+                rate: *rate,
+                connections,
+            })
+        }
+
+        let tunnels: HashMap<usize, Vec<usize>> =
+            (0..num).map(|i| (i, all[i].connections.clone())).collect();
+
+        // agents only set off from the start or from a valve they've just opened
+        let sources = major.iter().copied().chain([start_idx]);
+        let paths = graph::all_pairs(&AdjacencyMap::from(tunnels), sources);
+
+        Ok(Self {
+            all,
+            major,
+            paths,
+            num,
+            start_idx,
+        })
+    }
+}
+
+impl ValveNetwork {
+    fn search_for_best_action_sequence(
+        &self,
+        agents: Vec<AgentState>,
+        max_sim_time: u32,
+    ) -> NetworkState {
+        let init_queue: Vec<NetworkState> = NetworkState::starting(self.num, agents)
+            .future(self)
+            .iter()
+            .filter(|initial_network_state| {
+                if initial_network_state.agents.len() == 2 {
+                    initial_network_state.agents[0].targeting.unwrap()
+                        < initial_network_state.agents[1].targeting.unwrap()
+                } else {
+                    true
+                }
+            })
+            .cloned()
+            .collect();
+
+        // search progress is reported on stderr, keeping stdout for answers
+        stderr().execute(cursor::Hide).unwrap();
+        let best_per: Vec<_> = init_queue
+            .par_iter()
+            .enumerate()
+            .map(|(i, start)| self.best_sequence_from(start.clone(), max_sim_time, i))
+            .collect();
+        stderr().execute(cursor::Show).unwrap();
+        eprintln!();
+
+        best_per
+            .into_iter()
+            .max_by(|a, b| a.released_pressure.cmp(&b.released_pressure))
+            .unwrap()
+
+        // for (i, o) in best.opened.iter().enumerate() {
+        //     if valves.all[i].rate > 0 {
+        //         let n = if *o { "" } else { "not " };
+        //         println!(
+        //             "{} ({}) {}opened",
+        //             valves.all[i].name, valves.all[i].rate, n
+        //         );
+        //     }
+        // }
+    }
+
+    fn best_sequence_from(&self, init: NetworkState, max_sim_time: u32, id: usize) -> NetworkState {
+        let mut best = init.clone();
+        let mut queue: Vec<NetworkState> = vec![init];
+
+        let mut expanded: u64 = 0;
+        let max_real_time = 240;
+        let start = Instant::now();
+
+        while let Some(mut current) = queue.pop() {
+            let secs_passed = start.elapsed().as_secs();
+            if secs_passed > max_real_time {
+                break;
+            }
+            if current.minute < max_sim_time {
+                queue.append(&mut current.future(self));
+            }
+            if current.released_pressure > best.released_pressure {
+                best = current;
+            }
+            if start.elapsed().as_millis().is_multiple_of(1000) || queue.len() < 2 {
+                eprint!(
+                    "(instance {id}) expanded = {expanded}, time = {secs_passed}, queue size = {}, best pressure released = {}     \r",
+                    queue.len(),
+                    best.released_pressure
+                );
+            }
+            expanded += 1;
+        }
+
+        if !queue.is_empty() {
+            eprintln!("\nWarning: search terminated early.");
+        }
+
+        best
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Action {
+    Move(usize),
+    Open,
+    Stay,
+}
+
+#[derive(Debug, Clone)]
+struct AgentState {
+    currently_at: usize,
+    targeting: Option<usize>,
+    plan: VecDeque<Action>,
+    hist: Vec<Action>,
+}
+
+impl AgentState {
+    fn new(start_idx: usize) -> Self {
+        Self {
+            currently_at: start_idx,
+            targeting: None,
+            plan: VecDeque::with_capacity(32),
+            hist: Vec::with_capacity(32),
+        }
+    }
+
+    fn transition(&self, action: Action) -> Self {
+        let mut next = self.clone();
+        if let Action::Move(destination_idx) = action {
+            next.currently_at = destination_idx;
+        }
+        next.hist.push(action);
+        next
+    }
+
+    /// All possible (agent state) transitions
+    fn future(&self, valves: &ValveNetwork, remaining_major: HashSet<usize>) -> Vec<Self> {
+        if let Some(action) = self.plan.front() {
+            let mut new = self.transition(action.clone());
+            new.plan.pop_front();
+            return vec![new];
+        }
+
+        if remaining_major.is_empty() {
+            let mut next = self.transition(Action::Stay);
+            next.targeting = None;
+            return vec![next];
+        }
+
+        remaining_major
+            .iter()
+            .map(|&destination_idx| {
+                let path = valves.paths[&self.currently_at]
+                    .path_to(destination_idx)
+                    .expect("no tunnels lead to the valve");
+
+                let mut next = self.transition(Action::Move(path[1]));
+                next.targeting = Some(destination_idx);
+                for &future_dest in path[2..].iter() {
+                    next.plan.push_back(Action::Move(future_dest))
+                }
+                next.plan.push_back(Action::Open); // Open destination
+                next
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NetworkState {
+    minute: u32,
+    total_rate: u32,
+    released_pressure: u32,
+    targeted: Vec<bool>,
+    opened: Vec<bool>,
+    agents: Vec<AgentState>,
+}
+
+impl NetworkState {
+    fn starting(n: usize, agents: Vec<AgentState>) -> Self {
+        Self {
+            minute: 0,
+            total_rate: 0,
+            released_pressure: 0,
+            targeted: vec![false; n],
+            opened: vec![false; n],
+            agents,
+        }
+    }
+
+    fn transition(&self) -> Self {
+        Self {
+            minute: self.minute + 1,
+            total_rate: self.total_rate,
+            released_pressure: self.released_pressure + self.total_rate,
+            targeted: self.targeted.clone(),
+            opened: self.opened.clone(),
+            agents: vec![],
+        }
+    }
+
+    fn add_agent(&mut self, agent: AgentState, valves: &ValveNetwork) {
+        if let Some(t) = agent.targeting {
+            self.targeted[t] = true;
+        }
+        if let Some(Action::Open) = agent.hist.last() {
+            self.opened[agent.currently_at] = true;
+            self.total_rate += valves.all[agent.currently_at].rate;
+        }
+        self.agents.push(agent);
+    }
+
+    fn remaining_targets(&self, valves: &ValveNetwork) -> HashSet<usize> {
+        valves
+            .major
+            .iter()
+            .filter(|ni| !self.targeted[**ni])
+            .copied()
+            .collect()
+    }
+
+    /// All possible (network state) transitions
+    fn future(&mut self, valves: &ValveNetwork) -> Vec<NetworkState> {
+        let remaining_targets = self.remaining_targets(valves);
+
+        // TODO: arbitrary&neat multi agent
+        let mut network_states = vec![];
+        match self.agents.len() {
+            1 => {
+                for agent_state in self.agents[0].future(valves, remaining_targets) {
+                    let mut ns = self.transition();
+                    ns.add_agent(agent_state, valves);
+                    network_states.push(ns);
+                }
+            }
+
+            2 => {
+                for agent_1_state in self.agents[0].future(valves, remaining_targets.clone()) {
+                    let mut ns = self.transition();
+                    ns.add_agent(agent_1_state, valves);
+                    for agent_2_state in self.agents[1].future(valves, ns.remaining_targets(valves))
+                    {
+                        let mut ns2 = ns.clone();
+                        ns2.add_agent(agent_2_state, valves);
+                        network_states.push(ns2);
+                    }
+                }
+            }
+            n => {
+                panic!("unsuported number of agents ({n})")
+            }
+        }
+        network_states
+    }
+}
+
+/// The best sequence of actions found, replayed a minute at a time
+pub struct Replay<'a> {
+    valves: &'a ValveNetwork,
+    agents: Vec<AgentState>,
+    minute: usize,
+    /// Valve each agent is at
+    at: Vec<usize>,
+    opened: Vec<bool>,
+    total_rate: u32,
+    released: u32,
+    /// What each agent did in the last minute
+    events: Vec<String>,
+}
+
+impl<'a> Replay<'a> {
+    /// The best actions for part 1
+    pub fn best_alone(valves: &'a ValveNetwork) -> Self {
+        let agents = best_alone(valves).agents;
+        Replay {
+            valves,
+            at: vec![valves.start_idx; agents.len()],
+            agents,
+            minute: 0,
+            opened: vec![false; valves.num],
+            total_rate: 0,
+            released: 0,
+            events: vec![],
+        }
+    }
+}
+
+impl Animate for Replay<'_> {
+    fn step(&mut self) -> bool {
+        if self
+            .agents
+            .iter()
+            .any(|agent| agent.hist.len() <= self.minute)
+        {
+            return false;
+        }
+        self.released += self.total_rate;
+        self.events.clear();
+        for (j, agent) in self.agents.iter().enumerate() {
+            let event = match agent.hist[self.minute] {
+                Action::Move(id) => {
+                    self.at[j] = id;
+                    format!("moves to {}", self.valves.all[id].name)
+                }
+                Action::Open => {
+                    self.opened[self.at[j]] = true;
+                    self.total_rate += self.valves.all[self.at[j]].rate;
+                    format!("opens {}", self.valves.all[self.at[j]].name)
+                }
+                Action::Stay => "stays".to_string(),
+            };
+            self.events.push(format!("Agent {} {event}", j + 1));
+        }
+        self.minute += 1;
+        true
+    }
+
+    /// The working valves, and any others an agent is at, then what happened this minute
+    fn render(&self) -> Frame {
+        let mut lines: Vec<(String, Option<Color>)> = vec![];
+        for (i, valve) in self.valves.all.iter().enumerate() {
+            let here: Vec<String> = (0..self.at.len())
+                .filter(|&j| self.at[j] == i)
+                .map(|j| (j + 1).to_string())
+                .collect();
+            if valve.rate == 0 && here.is_empty() {
+                continue;
+            }
+            let state = if self.opened[i] { "open" } else { "closed" };
+            let mut line = format!("{} rate {:>2}  {state:<6}", valve.name, valve.rate);
+            if !here.is_empty() {
+                line += &format!("  <- agent {}", here.join(", "));
+            }
+            let colour = match (here.is_empty(), self.opened[i]) {
+                (false, _) => Some(Color::Yellow),
+                (true, true) => Some(Color::Green),
+                (true, false) => None,
+            };
+            lines.push((line, colour));
+        }
+        lines.push((String::new(), None));
+        lines.extend(self.events.iter().map(|event| (event.clone(), None)));
+
+        let width = lines.iter().map(|(line, _)| line.len()).max().unwrap_or(0);
+        let mut frame = Frame::new(lines.len(), width);
+        for (i, (line, colour)) in lines.iter().enumerate() {
+            for (j, c) in line.chars().enumerate() {
+                match colour {
+                    Some(colour) => frame.paint((i, j), c, *colour),
+                    None => frame.set((i, j), c),
+                }
+            }
+        }
+        frame.caption = format!(
+            "== Minute {} ==  releasing {} pressure, {} total",
+            self.minute, self.total_rate, self.released
+        );
+        frame
+    }
+}
+
+fn best_alone(valves: &ValveNetwork) -> NetworkState {
+    valves.search_for_best_action_sequence(vec![AgentState::new(valves.start_idx)], 30)
+}
+
+pub fn parse(input: &str) -> Result<ValveNetwork, ParseError> {
+    input.parse()
+}
+
+/// Most pressure that can be released in 30 minutes alone
+pub fn part1(valves: &ValveNetwork) -> u32 {
+    best_alone(valves).released_pressure
+}
+
+/// Most pressure that can be released in 26 minutes with an elephant's help
+pub fn part2(valves: &ValveNetwork) -> u32 {
+    valves
+        .search_for_best_action_sequence(vec![AgentState::new(valves.start_idx); 2], 26)
+        .released_pressure
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    type Parsed = ValveNetwork;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part1(valves: &Self::Parsed) -> String {
+        part1(valves).to_string()
+    }
+
+    fn part2(valves: &Self::Parsed) -> String {
+        part2(valves).to_string()
+    }
+
+    /// Replays the best sequence of actions for part 1
+    fn animations(valves: &Self::Parsed) -> Vec<Box<dyn Animate + '_>> {
+        vec![Box::new(Replay::best_alone(valves))]
+    }
+}
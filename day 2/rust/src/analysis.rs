@@ -0,0 +1,206 @@
+//! What else could be made of a strategy guide: the best reply to the opponent's habits, and
+//! how every other reading of the second column would score
+
+use std::fmt::{self, Display};
+use crate::{get_pos, Game, Outcome, UnknownSymbol};
+
+/// A way of reading the second column of the guide
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reading {
+    /// The symbol for each move, in the game's order
+    Moves(Vec<char>),
+    /// The symbol for each outcome: lose, draw and win
+    Outcomes([char; 3])
+}
+
+impl Reading {
+    /// Total score for `guide` read this way
+    pub fn total(&self, game: &Game, guide: &[(char, char)]) -> Result<u32, UnknownSymbol> {
+        let mut game = game.clone();
+        match self {
+            Reading::Moves(you) => {
+                game.you = you.clone();
+                game.total(guide, Game::score)
+            }
+            Reading::Outcomes(outcomes) => {
+                game.outcomes = *outcomes;
+                game.total(guide, Game::score2)
+            }
+        }
+    }
+
+    pub fn describe(&self, game: &Game) -> String {
+        let pairs: Vec<String> = match self {
+            Reading::Moves(you) => you.iter().zip(&game.moves).map(|(c, m)| format!("{c}={m}")).collect(),
+            Reading::Outcomes(outcomes) => outcomes.iter().zip(Outcome::ALL)
+                .map(|(c, o)| format!("{c}={o}"))
+                .collect()
+        };
+        pairs.join(" ")
+    }
+}
+
+/// Most moves for which every reading of the second column as moves is tried, as there are n! of
+/// them: 40320 for 8 moves
+pub const MAX_PERMUTED: usize = 8;
+
+/// Every ordering of `symbols`
+fn permutations(symbols: &[char]) -> Vec<Vec<char>> {
+    if symbols.is_empty() {
+        return vec![vec![]];
+    }
+    let mut all = vec![];
+    for (i, &first) in symbols.iter().enumerate() {
+        let mut rest = symbols.to_vec();
+        rest.remove(i);
+        for mut perm in permutations(&rest) {
+            perm.insert(0, first);
+            all.push(perm);
+        }
+    }
+    all
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis {
+    /// Rounds in which the opponent played each move
+    pub frequencies: Vec<usize>,
+    /// Your average score per round for each move, if you played it every round
+    pub expected: Vec<f64>,
+    /// The move with the highest expected score
+    pub best_response: usize,
+    /// Totals reading the second column as moves (`score`) and as outcomes (`score2`), with the
+    /// game's own symbols. None if it has a symbol that isn't one when read that way.
+    pub as_moves: Option<u32>,
+    pub as_outcomes: Option<u32>,
+    /// Every mapping of the second column to moves or to outcomes, highest total first. Mappings
+    /// to moves are left out for games of more than `MAX_PERMUTED` moves.
+    pub readings: Vec<(Reading, u32)>,
+    pub moves_permuted: bool
+}
+
+impl Analysis {
+    pub fn new(game: &Game, guide: &[(char, char)]) -> Result<Self, UnknownSymbol> {
+        let mut frequencies = vec![0; game.moves.len()];
+        for &(p1_move, _) in guide {
+            frequencies[get_pos(p1_move, &game.opponent)?] += 1;
+        }
+        let rounds = guide.len().max(1) as f64;
+        let expected: Vec<f64> = (0..game.moves.len())
+            .map(|p2_id| {
+                let total: usize = frequencies.iter().enumerate()
+                    .map(|(p1_id, &times)| times * game.round_score(p1_id, p2_id) as usize)
+                    .sum();
+                total as f64 / rounds
+            })
+            .collect();
+        let best_response = (0..expected.len())
+            .max_by(|&a, &b| expected[a].total_cmp(&expected[b]))
+            .unwrap();
+
+        let moves_permuted = game.you.len() <= MAX_PERMUTED;
+        let moves = if moves_permuted { permutations(&game.you) } else { vec![] }.into_iter().map(Reading::Moves);
+        let outcomes = permutations(&game.outcomes).into_iter()
+            .map(|perm| Reading::Outcomes(perm.try_into().unwrap()));
+        // readings that don't cover every symbol in the guide are left out
+        let mut readings: Vec<(Reading, u32)> = moves.chain(outcomes)
+            .filter_map(|reading| reading.total(game, guide).ok().map(|total| (reading, total)))
+            .collect();
+        readings.sort_by_key(|(_, total)| std::cmp::Reverse(*total));
+
+        Ok(Analysis {
+            frequencies,
+            expected,
+            best_response,
+            as_moves: game.total(guide, Game::score).ok(),
+            as_outcomes: game.total(guide, Game::score2).ok(),
+            readings,
+            moves_permuted
+        })
+    }
+
+    /// Whether the guide scores more when read as outcomes than as moves, or can only be read as
+    /// outcomes
+    pub fn prefers_outcomes(&self) -> bool {
+        self.as_outcomes > self.as_moves
+    }
+
+    pub fn display<'a>(&'a self, game: &'a Game) -> impl Display + 'a {
+        AnalysisDisplay { analysis: self, game }
+    }
+}
+
+struct AnalysisDisplay<'a> {
+    analysis: &'a Analysis,
+    game: &'a Game
+}
+
+impl Display for AnalysisDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let AnalysisDisplay { analysis, game } = self;
+        writeln!(f, "Opponent played:")?;
+        for (i, name) in game.moves.iter().enumerate() {
+            writeln!(f, "  {} {name:<10} {:>6} rounds", game.opponent[i], analysis.frequencies[i])?;
+        }
+        writeln!(f, "Points a round, always playing:")?;
+        for (i, name) in game.moves.iter().enumerate() {
+            writeln!(f, "  {} {name:<10} {:>6.2}", game.you[i], analysis.expected[i])?;
+        }
+        writeln!(f, "Best response: always play {}", game.moves[analysis.best_response])?;
+        let scores = |total: Option<u32>| total.map_or("can't be read".to_string(), |total| format!("scores {total}"));
+        let better = match (analysis.as_moves, analysis.as_outcomes) {
+            (None, None) => "it can't be read either way",
+            _ if analysis.prefers_outcomes() => "it scores best as outcomes",
+            _ => "it scores best as moves"
+        };
+        writeln!(f, "Read as moves the guide {}, as outcomes it {}: {better}",
+                 scores(analysis.as_moves), scores(analysis.as_outcomes))?;
+        if analysis.moves_permuted {
+            writeln!(f, "Every reading of the second column:")?;
+        } else {
+            writeln!(f, "Every reading of the second column as outcomes, {} moves having too many orders to try:",
+                     game.moves.len())?;
+        }
+        for (reading, total) in &analysis.readings {
+            writeln!(f, "  {total:>6}  {}", reading.describe(game))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input;
+    use crate::{analysis::*, parse, parse_guide};
+
+    #[test]
+    fn example_guide() {
+        let game = Game::rock_paper_scissors();
+        let analysis = Analysis::new(&game, &parse(&input::test_input()).unwrap().rounds).unwrap();
+        assert_eq!(analysis.frequencies, vec![1, 1, 1]);
+        assert_eq!(analysis.expected, vec![4.0, 5.0, 6.0]);
+        assert_eq!(game.moves[analysis.best_response], "scissors");
+        assert_eq!((analysis.as_moves, analysis.as_outcomes), (Some(15), Some(12)));
+        assert!(!analysis.prefers_outcomes());
+        // 6 ways to read XYZ as moves, and 6 as outcomes
+        assert_eq!(analysis.readings.len(), 12);
+        assert!(analysis.readings.contains(&(Reading::Moves(vec!['X', 'Y', 'Z']), 15)));
+        assert!(analysis.readings.contains(&(Reading::Outcomes(['X', 'Y', 'Z']), 12)));
+        assert!(analysis.readings.windows(2).all(|w| w[0].1 >= w[1].1));
+    }
+
+    #[test]
+    fn too_many_moves_to_permute() {
+        let moves: Vec<String> = (1..=9).map(|i| format!("\"move {i}\"")).collect();
+        let game: Game = format!("moves = [{}]\nscores = [1, 2, 3, 4, 5, 6, 7, 8, 9]\nopponent = \"ABCDEFGHI\"\n\
+                                  you = \"QRSTUVWXY\"\noutcomes = \"XYZ\"\noutcome_scores = [0, 3, 6]\n", moves.join(", "))
+            .parse().unwrap();
+        let guide = parse_guide("A X\nI Y\n", game).unwrap();
+        let analysis = Analysis::new(&guide.game, &guide.rounds).unwrap();
+        assert!(!analysis.moves_permuted);
+        assert_eq!(analysis.readings.len(), 6);
+        assert!(analysis.readings.iter().all(|(reading, _)| matches!(reading, Reading::Outcomes(_))));
+        // move 8 loses to move 1, then move 9 draws
+        assert_eq!(analysis.as_moves, Some(8 + 9 + 3));
+    }
+}
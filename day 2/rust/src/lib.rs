@@ -0,0 +1,364 @@
+//! Day 2: Rock Paper Scissors
+
+use std::{fmt::{self, Display}, error::Error, fs, str::FromStr};
+use serde::Deserialize;
+use aoc_common::{parse::ParseError, solution::{Options, Solution}};
+
+pub mod analysis;
+use analysis::Analysis;
+
+/// The puzzle's game, and the format of any other
+pub static ROCK_PAPER_SCISSORS: &str = include_str!("../games/rock_paper_scissors.toml");
+
+/// Games that can be loaded by name, as well as from a file
+pub static GAMES: [(&str, &str); 2] = [
+    ("rock_paper_scissors", ROCK_PAPER_SCISSORS),
+    ("rock_paper_scissors_lizard_spock", include_str!("../games/rock_paper_scissors_lizard_spock.toml"))
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win
+}
+use Outcome::*;
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Loss, Draw, Win];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Loss => write!(f, "lose"),
+            Draw => write!(f, "draw"),
+            Win  => write!(f, "win")
+        }
+    }
+}
+
+/// A symbol in a strategy guide that the game doesn't use
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownSymbol {
+    pub symbol: char,
+    pub expected: Vec<char>
+}
+
+impl Display for UnknownSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let expected: Vec<String> = self.expected.iter().map(char::to_string).collect();
+        write!(f, "unknown symbol {:?}, expected one of {}", self.symbol, expected.join(", "))
+    }
+}
+
+impl Error for UnknownSymbol {}
+
+/// Index of `symbol` in `symbols`
+pub fn get_pos(symbol: char, symbols: &[char]) -> Result<usize, UnknownSymbol> {
+    symbols.iter().position(|&s| s == symbol)
+        .ok_or_else(|| UnknownSymbol { symbol, expected: symbols.to_vec() })
+}
+
+/// A game like rock paper scissors with an odd number of moves, each beating half of the others
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub moves: Vec<String>,
+    /// Points for playing each move
+    pub scores: Vec<u32>,
+    /// Symbols for each move in the first column of a strategy guide
+    pub opponent: Vec<char>,
+    /// Symbols for each move in the second column
+    pub you: Vec<char>,
+    /// The second column read as the outcome to aim for, indexed by `Outcome`
+    pub outcomes: [char; 3],
+    pub outcome_scores: [u32; 3],
+    /// `beats[i][j]` when move i beats move j
+    beats: Vec<Vec<bool>>
+}
+
+/// A game as written in TOML, see `ROCK_PAPER_SCISSORS`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    moves: Vec<String>,
+    scores: Vec<u32>,
+    opponent: String,
+    you: String,
+    outcomes: String,
+    outcome_scores: [u32; 3],
+    /// (winner, loser) pairs, otherwise each move beats the half of the others before it
+    #[serde(default)]
+    beats: Vec<(String, String)>
+}
+
+impl FromStr for Game {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let config: Config = toml::from_str(s).map_err(|err| err.to_string())?;
+        Game::try_from(config)
+    }
+}
+
+impl TryFrom<Config> for Game {
+    type Error = String;
+
+    fn try_from(config: Config) -> Result<Self, Self::Error> {
+        let n = config.moves.len();
+        if n < 3 || n.is_multiple_of(2) {
+            return Err(format!("expected an odd number of moves, at least 3, got {n}"));
+        }
+        let opponent: Vec<char> = config.opponent.chars().collect();
+        let you: Vec<char> = config.you.chars().collect();
+        if config.scores.len() != n || opponent.len() != n || you.len() != n {
+            return Err(format!("expected a score and a symbol in each column for each of the {n} moves"));
+        }
+        let outcomes: [char; 3] = config.outcomes.chars().collect::<Vec<_>>().try_into()
+            .map_err(|_| format!("expected 3 outcome symbols for lose, draw and win, got {:?}", config.outcomes))?;
+
+        let beats = if config.beats.is_empty() {
+            (0..n).map(|i| (0..n).map(|j| (1..=n / 2).any(|d| (j + d) % n == i)).collect()).collect()
+        } else {
+            let mut beats = vec![vec![false; n]; n];
+            for (winner, loser) in &config.beats {
+                let index = |name: &String| config.moves.iter().position(|m| m == name)
+                    .ok_or_else(|| format!("unknown move {name:?} in beats"));
+                beats[index(winner)?][index(loser)?] = true;
+            }
+            beats
+        };
+        for (i, row) in beats.iter().enumerate() {
+            if let Some(j) = (0..n).find(|&j| j != i && row[j] == beats[j][i]) {
+                return Err(format!("exactly one of {} and {} should beat the other", config.moves[i], config.moves[j]));
+            }
+            if row[i] || row.iter().filter(|&&b| b).count() != n / 2 {
+                return Err(format!("{} should beat exactly {} other moves", config.moves[i], n / 2));
+            }
+        }
+
+        Ok(Game { moves: config.moves, scores: config.scores, opponent, you, outcomes,
+                  outcome_scores: config.outcome_scores, beats })
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Game::rock_paper_scissors()
+    }
+}
+
+impl Game {
+    pub fn rock_paper_scissors() -> Self {
+        ROCK_PAPER_SCISSORS.parse().expect("the puzzle's game is valid")
+    }
+
+    /// One of `GAMES` by name, or else the game in the TOML file at the path `name`
+    pub fn load(name: &str) -> Result<Self, String> {
+        match GAMES.iter().find(|(game, _)| *game == name) {
+            Some((_, toml)) => toml.parse(),
+            None => fs::read_to_string(name).map_err(|err| format!("could not read {name}, {err}"))?.parse()
+        }
+    }
+
+    /// The outcome for you playing move `p2_id` against `p1_id`
+    pub fn outcome(&self, p1_id: usize, p2_id: usize) -> Outcome {
+        if p1_id == p2_id {
+            Draw
+        } else if self.beats[p2_id][p1_id] {
+            Win
+        } else {
+            Loss
+        }
+    }
+
+    /// Your score for playing move `p2_id` against `p1_id`
+    pub fn round_score(&self, p1_id: usize, p2_id: usize) -> u32 {
+        self.scores[p2_id] + self.outcome_scores[self.outcome(p1_id, p2_id).index()]
+    }
+
+    /// Your score, reading the second column as the move to play
+    pub fn score(&self, p1_move: char, p2_move: char) -> Result<u32, UnknownSymbol> {
+        // p1 is opponent, p2 is "you"
+        let p1_id = get_pos(p1_move, &self.opponent)?;
+        let p2_id = get_pos(p2_move, &self.you)?;
+        Ok(self.round_score(p1_id, p2_id))
+    }
+
+    /// Your score, reading the second column as the outcome to aim for. Where more than one move
+    /// gives it, the highest scoring is played.
+    pub fn score2(&self, p1_move: char, outcome: char) -> Result<u32, UnknownSymbol> {
+        let p1_id = get_pos(p1_move, &self.opponent)?;
+        let outcome = Outcome::ALL[get_pos(outcome, &self.outcomes)?];
+        // every move has a draw against itself, and beats and loses to others
+        Ok((0..self.moves.len())
+            .filter(|&p2_id| self.outcome(p1_id, p2_id) == outcome)
+            .map(|p2_id| self.round_score(p1_id, p2_id))
+            .max()
+            .unwrap())
+    }
+
+    /// Total score for a strategy guide, as scored by `score` or `score2`
+    pub fn total(&self, guide: &[(char, char)], score: fn(&Game, char, char) -> Result<u32, UnknownSymbol>)
+        -> Result<u32, UnknownSymbol> {
+        guide.iter().map(|&(m1, m2)| score(self, m1, m2)).sum()
+    }
+}
+
+/// A strategy guide and the game it's for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guide {
+    pub game: Game,
+    /// Each round, as the opponent's move and the second column, which may be a move or an outcome
+    pub rounds: Vec<(char, char)>
+}
+
+/// A strategy guide for `game`, having checked every symbol is one the game uses
+pub fn parse_guide(contents: &str, game: Game) -> Result<Guide, ParseError> {
+    let mut moves:Vec<(char,char)> = vec![];
+    let mut second: Vec<char> = game.you.clone();
+    second.extend(game.outcomes.iter().filter(|c| !game.you.contains(c)));
+
+    for line in contents.lines() {
+        let line_chars = Vec::from_iter(line.chars());
+        if line.is_empty() {
+            continue;
+        }
+        if line_chars.len() != 3 || line_chars[1] != ' ' {
+            return Err(ParseError::new(contents, line, "expected \"<opponent move> <your move>\""));
+        }
+        get_pos(line_chars[0], &game.opponent)
+            .map_err(|err| ParseError::new(contents, &line[..line_chars[0].len_utf8()], err))?;
+        get_pos(line_chars[2], &second)
+            .map_err(|err| ParseError::new(contents, &line[line.len() - line_chars[2].len_utf8()..], err))?;
+        moves.push((line_chars[0], line_chars[2]));
+    }
+
+    Ok(Guide { game, rounds: moves })
+}
+
+/// The strategy guide for rock paper scissors, the opponent's moves being A, B or C and the
+/// second column X, Y or Z
+pub fn parse(contents: &str) -> Result<Guide, ParseError> {
+    parse_guide(contents, Game::rock_paper_scissors())
+}
+
+/// Total score, reading the second column as the move to play. Parsing allows symbols that are
+/// only outcomes, which have no score here.
+pub fn part1(guide: &Guide) -> Result<u32, UnknownSymbol> {
+    guide.game.total(&guide.rounds, Game::score)
+}
+
+/// Total score, reading the second column as the outcome to aim for. Parsing allows symbols that
+/// are only moves, which have no score here.
+pub fn part2(guide: &Guide) -> Result<u32, UnknownSymbol> {
+    guide.game.total(&guide.rounds, Game::score2)
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    type Parsed = Guide;
+    const OPTIONS: &'static [&'static str] = &["game"];
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    /// Reads the guide for the game `game` names, as for `Game::load`
+    fn parse_with(input: &str, options: &Options) -> Result<Self::Parsed, ParseError> {
+        match options.get("game") {
+            Some(name) => parse_guide(input, Game::load(name).map_err(|err| ParseError::option("game", name, err))?),
+            None => parse(input)
+        }
+    }
+
+    fn part1(guide: &Self::Parsed) -> String {
+        part1(guide).map_or_else(|err| format!("the guide can't be read as moves, {err}"), |total| total.to_string())
+    }
+
+    fn part2(guide: &Self::Parsed) -> String {
+        part2(guide).map_or_else(|err| format!("the guide can't be read as outcomes, {err}"), |total| total.to_string())
+    }
+
+    /// What else the guide could mean, as the puzzle doesn't say what the second column is
+    fn analyse(guide: &Self::Parsed) -> Option<String> {
+        let analysis = Analysis::new(&guide.game, &guide.rounds).expect("parse checks the opponent's moves");
+        let report = analysis.display(&guide.game).to_string();
+        Some(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input;
+    use crate::*;
+
+    static RPSLS: &str = include_str!("../games/rock_paper_scissors_lizard_spock.toml");
+
+    #[test]
+    fn unknown_symbols() {
+        let game = Game::rock_paper_scissors();
+        assert_eq!(game.score('A', 'Y'), Ok(8));
+        assert_eq!(game.score('D', 'Y'), Err(UnknownSymbol { symbol: 'D', expected: vec!['A', 'B', 'C'] }));
+        let err = parse("A Y\nB W\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    #[test]
+    fn five_moves() {
+        let game: Game = RPSLS.parse().unwrap();
+        let cyclic: Game = RPSLS.split("beats = ").next().unwrap().parse().unwrap();
+        assert_eq!(game, cyclic);
+
+        // spock against rock, then rock against paper
+        assert_eq!(game.score('A', 'W'), Ok(2 + 6));
+        assert_eq!(game.score('C', 'V'), Ok(1));
+        // the best win against rock is paper
+        assert_eq!(game.score2('A', 'Z'), Ok(3 + 6));
+
+        let guide = parse_guide("A W\nC V\nE Z\n", game.clone()).unwrap();
+        assert_eq!(part1(&guide), Ok(8 + 1 + 8));
+        assert!(parse(&input::test_input()).is_ok());
+    }
+
+    #[test]
+    fn game_option() {
+        let mut options = Options::default();
+        options.set("game", "rock_paper_scissors_lizard_spock");
+        let guide = Day2::parse_with("A W\nC V\nE Z\n", &options).unwrap();
+        assert_eq!(guide.game, RPSLS.parse().unwrap());
+        assert_eq!(Game::load("games/rock_paper_scissors.toml"), Ok(Game::rock_paper_scissors()));
+
+        options.set("game", "no_such_game.toml");
+        let err = Day2::parse_with("A X\n", &options).unwrap_err();
+        assert_eq!((err.line, err.text.as_str()), (0, "no_such_game.toml"));
+    }
+
+    #[test]
+    fn symbols_for_one_part() {
+        let mut options = Options::default();
+        options.set("game", "rock_paper_scissors_lizard_spock");
+        // W and V are only moves
+        let guide = Day2::parse_with("A W\nC V\nE Z\n", &options).unwrap();
+        assert_eq!(Day2::part1(&guide), "17");
+        assert_eq!(Day2::part2(&guide), "the guide can't be read as outcomes, unknown symbol 'W', expected one of X, Y, Z");
+        let report = Day2::analyse(&guide).unwrap();
+        assert!(report.contains("Read as moves the guide scores 17, as outcomes it can't be read: it scores best as moves"));
+    }
+
+    #[test]
+    fn invalid_games() {
+        let three = ROCK_PAPER_SCISSORS.replace("scores = [1, 2, 3]", "scores = [1, 2]");
+        assert!(three.parse::<Game>().is_err());
+        let four = RPSLS.replace("[\"rock\", \"spock\"", "[\"rock\"");
+        assert!(four.parse::<Game>().is_err());
+        let unbalanced = format!("{ROCK_PAPER_SCISSORS}beats = [[\"rock\", \"paper\"]]");
+        assert!(unbalanced.parse::<Game>().is_err());
+    }
+}
@@ -0,0 +1,58 @@
+//! Times the bitmask engine against the original sets of characters, on the puzzle input when
+//! there is one, otherwise a larger copy of the example: `cargo bench -p day_3 --features bench`
+
+use std::{
+    fs,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use aoc_common::input;
+use day_3::{naive, parse};
+
+const ITERATIONS: usize = 200;
+
+type Part = fn(&[String]) -> usize;
+
+/// Median time for `f` over `ITERATIONS` runs
+fn median(f: impl Fn() -> usize) -> Duration {
+    let mut samples: Vec<Duration> = (0..ITERATIONS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    samples.sort();
+    samples[ITERATIONS / 2]
+}
+
+fn main() {
+    let contents = fs::read_to_string(input::default_path(3)).unwrap_or_else(|_| {
+        fs::read_to_string(input::example_path(3))
+            .unwrap()
+            .repeat(50)
+    });
+    let rucksacks = parse(&contents).expect("valid input");
+
+    println!("{} rucksacks, median of {ITERATIONS} runs", rucksacks.len());
+    println!(
+        "{:<8}  {:>12}  {:>12}  {:>8}",
+        "part", "sets", "bitmasks", "speedup"
+    );
+    let parts: [(&str, Part, Part); 2] = [
+        ("part 1", naive::part1, |r| day_3::part1(r) as usize),
+        ("part 2", naive::part2, |r| day_3::part2(r) as usize),
+    ];
+    for (name, sets, bitmasks) in parts {
+        assert_eq!(sets(&rucksacks), bitmasks(&rucksacks));
+        let sets = median(|| sets(black_box(&rucksacks)));
+        let bitmasks = median(|| bitmasks(black_box(&rucksacks)));
+        println!(
+            "{name:<8}  {:>12.2?}  {:>12.2?}  {:>7.1}x",
+            sets,
+            bitmasks,
+            sets.as_secs_f64() / bitmasks.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! Day 3: Rucksack Reorganization
+
+use std::{fmt::{self, Display, Write}, ops::BitAnd};
+use aoc_common::{parse::ParseError, solution::Solution};
+
+#[cfg(any(test, feature = "bench"))]
+pub mod naive;
+
+/// a-z have priorities 1-26, A-Z 27-52
+pub fn priority(c: char) -> Option<u32> {
+    match c {
+        'a'..='z' => Some(c as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Some(c as u32 - 'A' as u32 + 27),
+        _ => None
+    }
+}
+
+/// The item with priority `p`, from 1 to 52
+pub fn item(p: u32) -> char {
+    if p <= 26 { (b'a' + p as u8 - 1) as char } else { (b'A' + p as u8 - 27) as char }
+}
+
+/// A set of item types, as a bit for each priority
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Items(pub u64);
+
+impl Items {
+    /// Every item type, the identity for intersections
+    pub const ALL: Items = Items(((1 << 52) - 1) << 1);
+
+    /// Item types in `items`, skipping anything that isn't a letter
+    pub fn new(items: &[u8]) -> Self {
+        Items(items.iter().filter_map(|&c| priority(c as char)).fold(0, |mask, p| mask | 1 << p))
+    }
+
+    pub fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Priorities of the item types, lowest first
+    pub fn priorities(self) -> impl Iterator<Item = u32> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            let p = bits.trailing_zeros();
+            bits &= bits.wrapping_sub(1);
+            (p < 64).then_some(p)
+        })
+    }
+
+    pub fn items(self) -> impl Iterator<Item = char> {
+        self.priorities().map(item)
+    }
+
+    pub fn priority_sum(self) -> u32 {
+        self.priorities().sum()
+    }
+}
+
+impl BitAnd for Items {
+    type Output = Items;
+
+    fn bitand(self, rhs: Items) -> Items {
+        Items(self.0 & rhs.0)
+    }
+}
+
+/// The item types as letters, e.g. "pL"
+impl Display for Items {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.items().try_for_each(|c| write!(f, "{c}"))
+    }
+}
+
+/// Item types found in every one of `rucksacks`, none if there are no rucksacks
+pub fn shared(rucksacks: impl IntoIterator<Item = Items>) -> Items {
+    rucksacks.into_iter().reduce(BitAnd::bitand).unwrap_or_default()
+}
+
+/// The rucksack's contents split into `n` compartments of equal size, or None if its items don't
+/// divide evenly between them
+pub fn compartments(rucksack: &str, n: usize) -> Option<impl Iterator<Item = Items> + '_> {
+    let (items, len) = (rucksack.as_bytes(), rucksack.len());
+    let compartment = move |i: usize| Items::new(&items[i * len / n..(i + 1) * len / n]);
+    (n > 0 && len.is_multiple_of(n)).then(|| (0..n).map(compartment))
+}
+
+/// Item types in every compartment of each rucksack, or None if any can't be split into `n`
+pub fn duplicates(rucksacks: &[String], n: usize) -> Option<Vec<Items>> {
+    rucksacks.iter().map(|r| compartments(r, n).map(shared)).collect()
+}
+
+/// Item types carried by every rucksack of each group of `size`, the last group taking any left
+/// over
+pub fn badges(rucksacks: &[String], size: usize) -> Vec<Items> {
+    rucksacks.chunks(size.max(1))
+        .map(|group| shared(group.iter().map(|r| Items::new(r.as_bytes()))))
+        .collect()
+}
+
+/// Each rucksack's items, one letter per item, half in each compartment
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    for line in input.lines() {
+        if let Some((i, c)) = line.char_indices().find(|&(_, c)| priority(c).is_none()) {
+            return Err(ParseError::new(input, &line[i..i + c.len_utf8()], "expected item letter a-z or A-Z"));
+        }
+        if !line.len().is_multiple_of(2) {
+            return Err(ParseError::new(input, line, "expected an even number of items, half in each compartment"));
+        }
+    }
+    Ok(input.lines().map(String::from).collect())
+}
+
+/// Sum of the priorities of items found in both compartments of each rucksack
+pub fn part1(rucksacks : &[String]) -> u32 {
+    duplicates(rucksacks, 2).expect("parse checks rucksacks split in two")
+        .into_iter()
+        .map(Items::priority_sum)
+        .sum()
+}
+
+/// Sum of the priorities of the badge item shared by each group of three rucksacks
+pub fn part2(rucksacks : &[String]) -> u32 {
+    badges(rucksacks, 3).into_iter().map(Items::priority_sum).sum()
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part1(rucksacks: &Self::Parsed) -> String {
+        let duplicate_sum = part1(rucksacks);
+        duplicate_sum.to_string()
+    }
+
+    fn part2(rucksacks: &Self::Parsed) -> String {
+        let badge_sum = part2(rucksacks);
+        badge_sum.to_string()
+    }
+
+    /// Lists the items in both compartments of each rucksack, and each group's badge
+    fn analyse(rucksacks: &Self::Parsed) -> Option<String> {
+        let duplicates = duplicates(rucksacks, 2).expect("parse checks rucksacks split in two");
+        let groups = rucksacks.chunks(3).zip(duplicates.chunks(3));
+        let mut report = String::new();
+        for ((group, shared), badge) in groups.zip(badges(rucksacks, 3)) {
+            for (rucksack, shared) in group.iter().zip(shared) {
+                writeln!(report, "{rucksack:<50} {shared}").unwrap();
+            }
+            writeln!(report, "  badge {badge}").unwrap();
+        }
+        Some(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{examples, input};
+    use crate::*;
+
+    #[test]
+    fn readme_examples() {
+        examples::check::<Day3>();
+    }
+
+    #[test]
+    fn any_sizes_agree_with_sets() {
+        let rucksacks = parse(&input::test_input()).unwrap();
+        assert_eq!(crate::part1(&rucksacks) as usize, naive::part1(&rucksacks));
+        assert_eq!(crate::part2(&rucksacks) as usize, naive::part2(&rucksacks));
+
+        assert_eq!(shared(compartments("abcXbcYc", 4).unwrap()).to_string(), "");
+        assert_eq!(shared(compartments("aXbaXcaXd", 3).unwrap()).to_string(), "aX");
+        let pairs: Vec<String> = badges(&rucksacks, 2).iter().map(Items::to_string).collect();
+        assert_eq!(pairs, ["frsFM", "qvwBT", "GJZ"]);
+        assert_eq!(badges(&rucksacks, 6)[0].len(), 0);
+        assert_eq!(Items::new(b"a-Z!").to_string(), "aZ");
+    }
+
+    #[test]
+    fn shared_items_report() {
+        let report = Day3::analyse(&parse("abca\nbdbe\ncfgc\n").unwrap()).unwrap();
+        assert_eq!(report, format!("{:<50} a\n{:<50} b\n{:<50} c\n  badge \n", "abca", "bdbe", "cfgc"));
+    }
+
+    #[test]
+    fn blank_and_uneven_rucksacks() {
+        // an empty rucksack shares nothing, rather than everything
+        assert_eq!(shared([]), Items::default());
+        let rucksacks = parse("\n").unwrap();
+        assert_eq!((crate::part1(&rucksacks), crate::part2(&rucksacks)), (0, 0));
+
+        // always exactly n compartments, so 4 items can't go in 3
+        assert_eq!(compartments("abab", 2).unwrap().count(), 2);
+        assert!(compartments("abcd", 3).is_none());
+        assert_eq!(duplicates(&["abcd".to_string(), "abc".to_string()], 2), None);
+        let err = parse("abab\nabc\n").unwrap_err();
+        assert_eq!((err.line, err.text.as_str()), (2, "abc"));
+    }
+}
@@ -0,0 +1,39 @@
+//! The original approach, with a set of characters per rucksack, kept to benchmark against
+
+use std::collections::BTreeSet;
+
+pub(crate) static ALPHABET : &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+pub fn priority(c : char) -> usize {
+    ALPHABET.find(c).unwrap_or_else(|| panic!("{c} not alphabetic")) + 1
+}
+
+// chars shared by all input strings
+pub fn unique_common_chars(strings: Vec<&str>) -> BTreeSet<char> {
+    let mut set = strings[0].chars().collect::<BTreeSet<char>>();
+    for string in &strings[1..] {
+        set.retain(|&c| string.contains(c));
+    }
+    set
+}
+
+pub fn unique_common_char(strings: Vec<&str>) -> char {
+    unique_common_chars(strings).pop_last().unwrap()
+}
+
+pub fn line_score(line : &str) -> usize {
+    let n = line.len();
+    let strings = vec![&line[0 .. n/2], &line[n/2 .. n]];
+    unique_common_chars(strings)
+            .iter().map(|&c| priority(c)).sum()
+
+}
+
+pub fn part1(rucksacks : &[String]) -> usize {
+    rucksacks.iter().map(|r| line_score(r)).sum()
+}
+
+pub fn part2(rucksacks : &[String]) -> usize {
+    rucksacks.chunks(3).map(|triplet| 
+                        priority(unique_common_char(triplet.iter().map(String::as_str).collect()))).sum()
+}
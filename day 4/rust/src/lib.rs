@@ -0,0 +1,167 @@
+//! Day 4: Camp Cleanup
+
+use std::fmt::Write;
+use aoc_common::{interval::{self, Interval, IntervalSet}, interval_tree::IntervalTree, parse::ParseError,
+                 solution::Solution};
+
+/// Inclusive range of section IDs, written "low-high"
+pub type Range = Interval<u32>;
+
+/// The sections assigned to a pair of elves
+pub type RangePair = (Range, Range);
+
+/// An elf, as the line of its pair and 0 or 1 for which of the pair
+pub type Elf = (usize, usize);
+
+fn range_pair(s: &str) -> Result<RangePair, ParseError> {
+    if let Some((r1s, r2s)) = s.split_once(',') {
+        let r1 = r1s.parse::<Range>().map_err(|err| err.within(s, r1s))?;
+        let r2 = r2s.parse::<Range>().map_err(|err| err.within(s, r2s))?;
+        Ok( (r1, r2) )
+    } else {
+        Err( ParseError::new(s, s, "expected \"low-high,low-high\" range pair") )
+    }
+}
+
+fn has_enclosing((r1, r2): &RangePair) -> bool {
+    r1.encloses(r2) || r2.encloses(r1)
+}
+
+fn has_overlap((r1, r2): &RangePair) -> bool {
+    r1.overlaps(r2)
+}
+
+fn ranges(pairs: &[RangePair]) -> impl Iterator<Item = Range> + '_ {
+    pairs.iter().flat_map(|(r1, r2)| [*r1, *r2])
+}
+
+/// Every section assigned to at least one elf
+pub fn covered(pairs: &[RangePair]) -> IntervalSet<u32> {
+    ranges(pairs).collect()
+}
+
+/// Sections between the lowest and highest assigned that nobody is assigned
+pub fn uncovered(pairs: &[RangePair]) -> IntervalSet<u32> {
+    let covered = covered(pairs);
+    match (covered.intervals().first(), covered.intervals().last()) {
+        (Some(first), Some(last)) => covered.gaps(Interval::new(first.low, last.high)),
+        _ => IntervalSet::new()
+    }
+}
+
+/// The first stretch of sections assigned to the most elves, and how many
+pub fn most_covered(pairs: &[RangePair]) -> Option<(Range, usize)> {
+    interval::max_coverage(ranges(pairs))
+}
+
+/// Every elf's range, for questions across all the pairs
+pub fn index(pairs: &[RangePair]) -> IntervalTree<u32, Elf> {
+    pairs.iter().enumerate().flat_map(|(i, (r1, r2))| [(*r1, (i, 0)), (*r2, (i, 1))]).collect()
+}
+
+/// Elves on different lines whose ranges overlap, each two once, in order
+pub fn overlapping_elves(index: &IntervalTree<u32, Elf>) -> Vec<(Elf, Elf)> {
+    let mut pairs: Vec<(Elf, Elf)> = index.overlapping_pairs().into_iter()
+        .map(|((_, a), (_, b))| (*a.min(b), *a.max(b)))
+        .filter(|(a, b)| a.0 != b.0)
+        .collect();
+    pairs.sort();
+    pairs
+}
+
+/// Elves whose every section is assigned to some other elf as well, by one range enclosing theirs.
+/// Of elves with the same range, the first is kept.
+pub fn redundant_elves(index: &IntervalTree<u32, Elf>) -> Vec<Elf> {
+    let mut elves: Vec<Elf> = index.redundant().into_iter().map(|(_, elf)| *elf).collect();
+    elves.sort();
+    elves
+}
+
+/// Each line's pair of ranges
+pub fn parse(input: &str) -> Result<Vec<RangePair>, ParseError> {
+    input.lines().enumerate()
+        .map(|(i, line)| range_pair(line).map_err(|err| err.offset_lines(i)))
+        .collect()
+}
+
+/// Number of pairs where one range encloses the other
+pub fn part1(pairs: &[RangePair]) -> usize {
+    pairs.iter().filter(|p| has_enclosing(p)).count()
+}
+
+/// Number of pairs which overlap at all
+pub fn part2(pairs: &[RangePair]) -> usize {
+    pairs.iter().filter(|p| has_overlap(p)).count()
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    type Parsed = Vec<RangePair>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part1(pairs: &Self::Parsed) -> String {
+        part1(pairs).to_string()
+    }
+
+    fn part2(pairs: &Self::Parsed) -> String {
+        part2(pairs).to_string()
+    }
+
+    /// Summarises the assignments across every pair
+    fn analyse(pairs: &Self::Parsed) -> Option<String> {
+        let covered = covered(pairs);
+        let mut report = format!("{} sections assigned, in {} stretches\n", covered.len(), covered.intervals().len());
+        let gaps: Vec<String> = uncovered(pairs).intervals().iter().map(Range::to_string).collect();
+        writeln!(report, "Unassigned: {}", if gaps.is_empty() { "none".to_string() } else { gaps.join(", ") }).unwrap();
+        if let Some((range, elves)) = most_covered(pairs) {
+            writeln!(report, "Most assigned: {range}, to {elves} elves").unwrap();
+        }
+        let index = index(pairs);
+        writeln!(report, "{} pairs of elves on different lines overlap", overlapping_elves(&index).len()).unwrap();
+        writeln!(report, "{} of {} elves are redundant", redundant_elves(&index).len(), index.len()).unwrap();
+        Some(report)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::examples;
+    use crate::*;
+
+    #[test]
+    fn readme_examples() {
+        examples::check::<Day4>();
+    }
+
+    #[test]
+    fn across_pairs() {
+        let pairs = parse("2-4,6-8\n11-12,3-5\n4-5,5-6").unwrap();
+        let gaps: Vec<String> = uncovered(&pairs).intervals().iter().map(Range::to_string).collect();
+        assert_eq!(gaps, ["9-10"]);
+        assert_eq!(covered(&pairs).len(), 9);
+        assert_eq!(most_covered(&pairs), Some((Interval::new(4, 4), 3)));
+
+        let index = index(&pairs);
+        assert_eq!(index.stabbing(5).len(), 3);
+        assert_eq!(overlapping_elves(&index), [((0, 0), (1, 1)), ((0, 0), (2, 0)), ((0, 1), (2, 1)),
+                                               ((1, 1), (2, 0)), ((1, 1), (2, 1))]);
+        assert_eq!(redundant_elves(&index), [(2, 0)]);
+
+        assert_eq!(Day4::analyse(&pairs).unwrap(), "9 sections assigned, in 2 stretches\n\
+                                                   Unassigned: 9-10\n\
+                                                   Most assigned: 4-4, to 3 elves\n\
+                                                   5 pairs of elves on different lines overlap\n\
+                                                   1 of 6 elves are redundant\n");
+    }
+
+    #[test]
+    fn bad_range() {
+        let err = Day4::parse("2-4,6-8\n2-3,4-x5").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 7, "x5"));
+    }
+}
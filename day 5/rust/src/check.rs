@@ -0,0 +1,107 @@
+//! Checking that steps of the procedure can be carried out, before or while running them
+
+use std::{error::Error, fmt::{self, Display}};
+use crate::{Instruction, Stack};
+
+/// Why a step can't be carried out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Illegal {
+    /// Stacks are numbered from 1 to the number drawn
+    NoSuchStack { stacks: usize },
+    TooFewCrates { needed: usize, available: usize }
+}
+
+impl Display for Illegal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Illegal::NoSuchStack { stacks } => write!(f, "there are only stacks 1 to {stacks}"),
+            Illegal::TooFewCrates { needed, available } => write!(f, "it has {available} crates, {needed} are moved")
+        }
+    }
+}
+
+/// A step that can't be carried out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveError {
+    /// Index of the step in the procedure, from 0
+    pub step: usize,
+    pub instruction: String,
+    /// The stack at fault, numbered from 1
+    pub stack: usize,
+    pub reason: Illegal
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "step {} \"{}\" is illegal at stack {}: {}", self.step + 1, self.instruction, self.stack, self.reason)
+    }
+}
+
+impl Error for MoveError {}
+
+impl MoveError {
+    fn new(step: usize, instruction: &Instruction, (stack, reason): (usize, Illegal)) -> Self {
+        MoveError { step, instruction: instruction.to_string(), stack, reason }
+    }
+}
+
+/// Whether `instruction` can be carried out on stacks of these `heights`, or which stack is at
+/// fault and why. It can whatever the crane, as every crane moves the same number of crates.
+fn check_heights(heights: &[usize], &Instruction { qty, origin, dest }: &Instruction) -> Result<(), (usize, Illegal)> {
+    for stack in [origin, dest] {
+        if stack == 0 || stack > heights.len() {
+            return Err((stack, Illegal::NoSuchStack { stacks: heights.len() }));
+        }
+    }
+    if heights[origin - 1] < qty {
+        return Err((origin, Illegal::TooFewCrates { needed: qty, available: heights[origin - 1] }));
+    }
+    Ok(())
+}
+
+/// Whether the step at index `step` can be carried out on `stacks` as they are
+pub fn check(stacks: &[Stack], step: usize, instruction: &Instruction) -> Result<(), MoveError> {
+    let heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+    check_heights(&heights, instruction).map_err(|fault| MoveError::new(step, instruction, fault))
+}
+
+/// Checks every step without running them, by following only the height of each stack. Returns
+/// the first that couldn't be carried out.
+pub fn validate(stacks: &[Stack], instructions: &[Instruction]) -> Result<(), MoveError> {
+    let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+    for (step, instruction) in instructions.iter().enumerate() {
+        check_heights(&heights, instruction).map_err(|fault| MoveError::new(step, instruction, fault))?;
+        heights[instruction.origin - 1] -= instruction.qty;
+        heights[instruction.dest - 1] += instruction.qty;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input;
+    use crate::{check::*, crane::*, parse};
+
+    #[test]
+    fn illegal_moves() {
+        // parse validates the procedure, so it's changed after
+        let (stacks, mut instructions) = parse(&input::test_input()).unwrap();
+        assert_eq!(validate(&stacks, &instructions), Ok(()));
+
+        // the step before empties stack 1
+        instructions[2].origin = 1;
+        instructions[2].qty = 3;
+        let err = validate(&stacks, &instructions).unwrap_err();
+        assert_eq!((err.step, err.stack, err.reason), (2, 1, Illegal::TooFewCrates { needed: 3, available: 0 }));
+        assert_eq!(err.to_string(), "step 3 \"move 3 from 1 to 1\" is illegal at stack 1: it has 0 crates, 3 are moved");
+
+        let mut rearranged = stacks.clone();
+        assert_eq!(CrateMover9001.try_rearrange(&mut rearranged, &instructions), Err(err));
+        // stopped before the illegal step
+        assert_eq!(rearranged, [vec![], vec!['M', 'C'], vec!['P', 'Z', 'N', 'D']]);
+
+        instructions[0].dest = 4;
+        let err = check(&stacks, 0, &instructions[0]).unwrap_err();
+        assert_eq!((err.stack, err.reason), (4, Illegal::NoSuchStack { stacks: 3 }));
+    }
+}
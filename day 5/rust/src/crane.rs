@@ -0,0 +1,224 @@
+//! Crane models, each with its own rule for carrying out a rearrangement step
+
+use std::fmt::{self, Display};
+use crate::{check::{self, MoveError}, Instruction, Stack};
+
+/// A crane that can carry out the rearrangement procedure
+pub trait Crane: Display {
+    /// Carries out a single step, moving as many of the crates as there are. Panics if either
+    /// stack doesn't exist.
+    fn apply(&self, stacks: &mut [Stack], instruction: &Instruction);
+
+    /// Puts back a step carried out by `apply`, whose `qty` is the crates it actually moved,
+    /// taking them off the destination the way they went on
+    fn undo(&self, stacks: &mut [Stack], instruction: &Instruction);
+
+    /// Carries out every step in turn
+    fn rearrange(&self, stacks: &mut [Stack], instructions: &[Instruction]) {
+        for instruction in instructions {
+            self.apply(stacks, instruction);
+        }
+    }
+
+    /// Carries out every step in turn, checking each first. Stops at the first that can't be
+    /// carried out, leaving the stacks as they were before it.
+    fn try_rearrange(&self, stacks: &mut [Stack], instructions: &[Instruction]) -> Result<(), MoveError> {
+        for (step, instruction) in instructions.iter().enumerate() {
+            check::check(stacks, step, instruction)?;
+            self.apply(stacks, instruction);
+        }
+        Ok(())
+    }
+}
+
+/// Takes up to `qty` crates off the top of `stack`, bottom first
+fn lift(stack: &mut Stack, qty: usize) -> Vec<char> {
+    stack.split_off(stack.len().saturating_sub(qty))
+}
+
+/// Moves the step's crates in lifts of at most `capacity`, each keeping its order, and lowers
+/// each lift to `depth` crates below the top of the destination
+fn move_crates(stacks: &mut [Stack], &Instruction { qty, origin, dest }: &Instruction, capacity: usize, depth: usize) {
+    let mut left = qty;
+    while left > 0 {
+        let n = left.min(capacity);
+        let load = lift(&mut stacks[origin - 1], n);
+        let stack = &mut stacks[dest - 1];
+        let at = stack.len().saturating_sub(depth);
+        stack.splice(at..at, load);
+        left -= n;
+    }
+}
+
+/// Takes back the crates `move_crates` moved, `qty` being how many there were, last lift first
+fn unmove_crates(stacks: &mut [Stack], &Instruction { qty, origin, dest }: &Instruction, capacity: usize, depth: usize) {
+    let mut left = qty;
+    while left > 0 {
+        // every lift but the last was full
+        let n = if left.is_multiple_of(capacity) { capacity } else { left % capacity };
+        let stack = &mut stacks[dest - 1];
+        let at = (stack.len() - n).saturating_sub(depth);
+        let load: Vec<char> = stack.drain(at..at + n).collect();
+        stacks[origin - 1].extend(load);
+        left -= n;
+    }
+}
+
+/// Moves crates one at a time, reversing their order
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn apply(&self, stacks: &mut [Stack], instruction: &Instruction) {
+        move_crates(stacks, instruction, 1, 0);
+    }
+
+    fn undo(&self, stacks: &mut [Stack], instruction: &Instruction) {
+        unmove_crates(stacks, instruction, 1, 0);
+    }
+}
+
+/// Moves several crates at once, keeping their order
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn apply(&self, stacks: &mut [Stack], instruction: &Instruction) {
+        move_crates(stacks, instruction, usize::MAX, 0);
+    }
+
+    fn undo(&self, stacks: &mut [Stack], instruction: &Instruction) {
+        unmove_crates(stacks, instruction, usize::MAX, 0);
+    }
+}
+
+/// Moves at most `capacity` crates per lift, keeping the order within each. A capacity of 1 is
+/// the CrateMover 9000, and one at least as large as any step the CrateMover 9001.
+#[derive(Debug, Clone, Copy)]
+pub struct Limited {
+    pub capacity: usize
+}
+
+impl Crane for Limited {
+    fn apply(&self, stacks: &mut [Stack], instruction: &Instruction) {
+        move_crates(stacks, instruction, self.capacity.max(1), 0);
+    }
+
+    fn undo(&self, stacks: &mut [Stack], instruction: &Instruction) {
+        unmove_crates(stacks, instruction, self.capacity.max(1), 0);
+    }
+}
+
+/// Moves several crates at once like the CrateMover 9001, but slides them in `depth` crates below
+/// the top of the destination, or at the bottom of a stack not that tall
+#[derive(Debug, Clone, Copy)]
+pub struct Inserting {
+    pub depth: usize
+}
+
+impl Crane for Inserting {
+    fn apply(&self, stacks: &mut [Stack], instruction: &Instruction) {
+        move_crates(stacks, instruction, usize::MAX, self.depth);
+    }
+
+    fn undo(&self, stacks: &mut [Stack], instruction: &Instruction) {
+        unmove_crates(stacks, instruction, usize::MAX, self.depth);
+    }
+}
+
+impl Display for CrateMover9000 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "9000")
+    }
+}
+
+impl Display for CrateMover9001 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "9001")
+    }
+}
+
+impl Display for Limited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "limited:{}", self.capacity)
+    }
+}
+
+impl Display for Inserting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "insert:{}", self.depth)
+    }
+}
+
+/// A crane chosen by name: "9000", "9001", "limited:<capacity>" or "insert:<depth>", as each
+/// crane displays itself
+pub fn model(name: &str) -> Result<Box<dyn Crane>, String> {
+    let number = |n: &str| n.parse::<usize>().map_err(|err| format!("invalid crane {name:?}, {err}"));
+    match name.split_once(':') {
+        None if name == "9000" => Ok(Box::new(CrateMover9000)),
+        None if name == "9001" => Ok(Box::new(CrateMover9001)),
+        Some(("limited", capacity)) => match number(capacity)? {
+            0 => Err(format!("invalid crane {name:?}, it must lift at least one crate")),
+            capacity => Ok(Box::new(Limited { capacity }))
+        },
+        Some(("insert", depth)) => Ok(Box::new(Inserting { depth: number(depth)? })),
+        _ => Err(format!("unknown crane {name:?}, expected 9000, 9001, limited:<capacity> or insert:<depth>"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input;
+    use crate::{crane::*, parse, stack_tops};
+
+    fn tops(crane: &dyn Crane) -> String {
+        let (mut stacks, instructions) = parse(&input::test_input()).unwrap();
+        crane.rearrange(&mut stacks, &instructions);
+        stack_tops(&stacks)
+    }
+
+    #[test]
+    fn models() {
+        assert_eq!(tops(&Limited { capacity: 1 }), tops(&CrateMover9000));
+        assert_eq!(tops(&Limited { capacity: 3 }), tops(&CrateMover9001));
+        // the second step lifts 2 crates then 1, leaving Z on top
+        assert_eq!(tops(&Limited { capacity: 2 }), "MCZ");
+        assert_eq!(tops(&Inserting { depth: 0 }), "MCD");
+
+        let mut stacks = vec![vec!['A', 'B'], vec!['C', 'D', 'E']];
+        Inserting { depth: 2 }.apply(&mut stacks, &Instruction { qty: 2, origin: 2, dest: 1 });
+        assert_eq!(stacks, [vec!['D', 'E', 'A', 'B'], vec!['C']]);
+    }
+
+    #[test]
+    fn undo() {
+        let cranes: [&dyn Crane; 5] = [&CrateMover9000, &CrateMover9001, &Limited { capacity: 2 },
+                                       &Inserting { depth: 1 }, &Inserting { depth: 5 }];
+        for crane in cranes {
+            let (stacks, instructions) = parse(&input::test_input()).unwrap();
+            let mut undone = stacks.clone();
+            crane.rearrange(&mut undone, &instructions);
+            for instruction in instructions.iter().rev() {
+                crane.undo(&mut undone, instruction);
+            }
+            assert_eq!(undone, stacks, "crane {crane}");
+        }
+        // 5 crates lifted 2, 2 then 1
+        let mut stacks = vec![vec!['A', 'B', 'C', 'D', 'E'], vec![]];
+        let instruction = Instruction { qty: 5, origin: 1, dest: 2 };
+        Limited { capacity: 2 }.apply(&mut stacks, &instruction);
+        assert_eq!(stacks[1], ['D', 'E', 'B', 'C', 'A']);
+        Limited { capacity: 2 }.undo(&mut stacks, &instruction);
+        assert_eq!(stacks[0], ['A', 'B', 'C', 'D', 'E']);
+    }
+
+    #[test]
+    fn by_name() {
+        for name in ["9000", "9001", "limited:4", "insert:1"] {
+            assert_eq!(model(name).unwrap().to_string(), name);
+        }
+        for name in ["9002", "limited:0", "insert:x", "limited"] {
+            assert!(model(name).is_err());
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! Day 5: Supply Stacks
+
+use std::{fmt::{self, Display}, str::FromStr};
+use aoc_common::{animation::Animate, parse::{self, ParseError}, solution::{Options, Solution}};
+
+pub mod check;
+pub mod crane;
+pub mod replay;
+use crane::{Crane, CrateMover9000, CrateMover9001};
+use replay::Replay;
+
+fn get_crates(line: &str) -> Vec<Option<char>> {
+    let mut crate_row = vec![];
+    let mut lc = line.chars();
+    while let (Some(c1), Some(c2), Some(c3), _) = (lc.next(),lc.next(),lc.next(),lc.next()) {
+        if c1=='[' && c3==']' {
+            crate_row.push(Some(c2));
+        } else {
+            crate_row.push(None);
+        }
+    }
+    crate_row
+}
+
+/// "move qty from origin to dest"
+#[derive(Debug)]
+pub struct Instruction {
+    pub qty: usize,
+    pub origin: usize, // 1 indexed as in text
+    pub dest: usize    //
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut sp1 = parse::after(s, "move ")?.split(" from ");
+        if let (Some(l), Some(r)) = (sp1.next(), sp1.next()) {
+            let mut sp2 = r.split(" to ");
+            if let (Some(m), Some(rr)) = (sp2.next(), sp2.next()) {
+                return Ok( Self{ qty: parse::field(s, l)?, origin: parse::field(s, m)?, dest: parse::field(s, rr)? } );
+            }   
+        }
+        Err( ParseError::new(s, s, "expected \"move <qty> from <stack> to <stack>\"") )
+    }
+}
+
+/// As written in the input
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.qty, self.origin, self.dest)
+    }
+}
+
+/// Crates from bottom to top
+pub type Stack = Vec<char>;
+
+/// The stacks drawn as in the input, a line per layer of crates padded to the full width, then
+/// the number line. Parsing the drawing gives back the same stacks.
+pub fn drawing(stacks: &[Stack]) -> String {
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut lines: Vec<String> = (0..height).rev()
+        .map(|level| {
+            let row: Vec<String> = stacks.iter()
+                .map(|stack| stack.get(level).map_or("   ".to_string(), |c| format!("[{c}]")))
+                .collect();
+            row.join(" ")
+        })
+        .collect();
+    let numbers: Vec<String> = (1..=stacks.len()).map(|i| format!("{i:^3}")).collect();
+    lines.push(numbers.join(" "));
+    lines.iter().map(|line| format!("{line}\n")).collect()
+}
+
+/// The starting stacks, numbered from 1 in the drawing, and the rearrangement procedure, having
+/// checked every step can be carried out
+pub fn parse(contents: &str) -> Result<(Vec<Stack>, Vec<Instruction>), ParseError> {
+    let mut stacks: Vec<Stack> = vec![];
+    let mut crate_rows: Vec<Vec<Option<char>>> = vec![];
+    let mut lines = contents.lines();
+
+    for l in lines.by_ref() {
+        if !l.contains('[') {
+            break; // consumes one non bracketed line (number line)
+        }
+        crate_rows.push(get_crates(l));
+    } 
+
+    // rows can be trimmed short, so the widest gives the number of stacks
+    let n_stacks = crate_rows.iter().map(|row| row.len()).max()
+        .ok_or_else(|| ParseError::new(contents, "", "expected a drawing of stacked [crates]"))?;
+    for _ in 0..n_stacks {
+        stacks.push(vec![]);
+    }
+
+    let crate_rows_len = crate_rows.len();
+    crate_rows.reverse(); // we read from top, fill stacks from bottom
+    for row in crate_rows {
+        for (i, opt) in row.iter().enumerate() {
+            if let Some(c) = opt {
+                stacks[i].push(*c);
+            }
+        }
+    }
+
+    // parse move instructions
+    lines.next(); // usually an empty line between crates and instructions?
+    let first_instruction = crate_rows_len + 2;
+
+    let lines: Vec<&str> = lines.collect();
+    let instructions: Vec<Instruction> = lines.iter().enumerate()
+        .map(|(i, l)| l.parse::<Instruction>().map_err(|err| err.offset_lines(first_instruction + i)))
+        .collect::<Result<_, _>>()?;
+    check::validate(&stacks, &instructions).map_err(|err| ParseError::new(contents, lines[err.step], err))?;
+
+    Ok((stacks, instructions))
+
+}
+
+/// The crate on top of each stack, or a space for an empty one
+pub fn stack_tops(stacks: &[Stack]) -> String {
+    String::from_iter(stacks.iter().map(|s| s.last().copied().unwrap_or(' ')))
+}
+
+/// Top crates after rearranging a copy of the stacks with `crane`
+pub fn rearranged(stacks: &[Stack], instructions: &[Instruction], crane: &dyn Crane) -> String {
+    let mut stacks = stacks.to_vec();
+    crane.rearrange(&mut stacks, instructions);
+    stack_tops(&stacks)
+}
+
+/// The starting stacks and the procedure, with the crane that carries it out for each part
+pub struct Rearrangement {
+    pub stacks: Vec<Stack>,
+    pub instructions: Vec<Instruction>,
+    pub cranes: [Box<dyn Crane>; 2]
+}
+
+/// Top crates after rearranging with the CrateMover 9000
+pub fn part1(stacks: &[Stack], instructions: &[Instruction]) -> String {
+    rearranged(stacks, instructions, &CrateMover9000)
+}
+
+/// Top crates after rearranging with the CrateMover 9001
+pub fn part2(stacks: &[Stack], instructions: &[Instruction]) -> String {
+    rearranged(stacks, instructions, &CrateMover9001)
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    type Parsed = Rearrangement;
+    const OPTIONS: &'static [&'static str] = &["crane"];
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Self::parse_with(input, &Options::default())
+    }
+
+    /// Both parts use the crane `crane` names, as for `crane::model`, rather than their own
+    fn parse_with(input: &str, options: &Options) -> Result<Self::Parsed, ParseError> {
+        let (stacks, instructions) = parse(input)?;
+        let cranes: [Box<dyn Crane>; 2] = match options.get("crane") {
+            Some(name) => {
+                let model = || crane::model(name).map_err(|err| ParseError::option("crane", name, err));
+                [model()?, model()?]
+            }
+            None => [Box::new(CrateMover9000), Box::new(CrateMover9001)]
+        };
+        Ok(Rearrangement { stacks, instructions, cranes })
+    }
+
+    fn part1(parsed: &Self::Parsed) -> String {
+        rearranged(&parsed.stacks, &parsed.instructions, parsed.cranes[0].as_ref())
+    }
+
+    fn part2(parsed: &Self::Parsed) -> String {
+        rearranged(&parsed.stacks, &parsed.instructions, parsed.cranes[1].as_ref())
+    }
+
+    /// The procedure replayed a step at a time by each part's crane
+    fn animations(parsed: &Self::Parsed) -> Vec<Box<dyn Animate + '_>> {
+        let Rearrangement { stacks, instructions, cranes: [first, second] } = parsed;
+        vec![Box::new(Replay::new(stacks, instructions, first.as_ref())),
+             Box::new(Replay::new(stacks, instructions, second.as_ref()))]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{examples, input};
+    use crate::*;
+
+    #[test]
+    fn readme_examples() {
+        examples::check::<Day5>();
+    }
+
+    #[test]
+    fn round_trip() {
+        let contents = input::test_input();
+        let (stacks, instructions) = parse(&contents).unwrap();
+        let steps: Vec<String> = instructions.iter().map(Instruction::to_string).collect();
+        assert_eq!(drawing(&stacks) + "\n" + &steps.join("\n"), contents);
+
+        let (mut stacks, instructions) = parse(&input::test_input()).unwrap();
+        CrateMover9000.apply(&mut stacks, &instructions[0]);
+        assert_eq!(drawing(&stacks), "[D]        \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n");
+        assert_eq!(parse(&(drawing(&stacks) + "\n")).unwrap().0, stacks);
+    }
+
+    #[test]
+    fn crane_option() {
+        let mut options = Options::default();
+        options.set("crane", "limited:2");
+        let parsed = Day5::parse_with(&input::test_input(), &options).unwrap();
+        assert_eq!((Day5::part1(&parsed), Day5::part2(&parsed)), ("MCZ".to_string(), "MCZ".to_string()));
+        assert_eq!(Day5::animations(&parsed).len(), 2);
+
+        options.set("crane", "9002");
+        let err = Day5::parse_with(&input::test_input(), &options).err().unwrap();
+        assert_eq!(err.text, "9002");
+    }
+
+    #[test]
+    fn bad_instruction() {
+        let tcontents = input::test_input().replace("move 2 from 2 to 1", "move 2 from two to 1");
+        let err = parse(&tcontents).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (8, 13, "two"));
+    }
+
+    #[test]
+    fn impossible_procedure() {
+        let tcontents = input::test_input().replace("move 1 from 1 to 2", "move 1 from 4 to 1");
+        let err = Day5::parse(&tcontents).err().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (9, 1, "move 1 from 4 to 1"));
+        assert!(err.reason.ends_with("is illegal at stack 4: there are only stacks 1 to 3"));
+
+        // stack 1 only holds 2 crates
+        let tcontents = input::test_input().replace("move 1 from 2 to 1", "move 5 from 1 to 2");
+        let err = Day5::parse(&tcontents).err().unwrap();
+        assert_eq!(err.line, 6);
+    }
+}
@@ -0,0 +1,142 @@
+//! Replaying the rearrangement a step at a time, with a log of each step to undo it by, so the
+//! player can step back and go to any step without recording frames
+
+use aoc_common::animation::{Animate, Color, Frame};
+use crate::{crane::Crane, drawing, Instruction, Stack};
+
+/// What a step moved, enough for the crane to move it back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Undo {
+    /// Stacks numbered from 1, as in the step
+    pub origin: usize,
+    pub dest: usize,
+    /// Crates taken off the origin, fewer than the step says if it ran out
+    pub moved: usize
+}
+
+impl Undo {
+    /// The step as it was carried out, for `Crane::undo`
+    fn instruction(self) -> Instruction {
+        Instruction { qty: self.moved, origin: self.origin, dest: self.dest }
+    }
+}
+
+/// The stacks part way through the procedure, as rearranged by `crane`
+pub struct Replay<'a> {
+    crane: &'a dyn Crane,
+    instructions: &'a [Instruction],
+    stacks: Vec<Stack>,
+    /// One entry per step taken, the last step's last
+    log: Vec<Undo>
+}
+
+impl<'a> Replay<'a> {
+    pub fn new(stacks: &[Stack], instructions: &'a [Instruction], crane: &'a dyn Crane) -> Self {
+        Replay { crane, instructions, stacks: stacks.to_vec(), log: vec![] }
+    }
+
+    pub fn stacks(&self) -> &[Stack] {
+        &self.stacks
+    }
+
+    pub fn log(&self) -> &[Undo] {
+        &self.log
+    }
+
+    /// Takes the next step, or returns false if there are none left
+    pub fn forward(&mut self) -> bool {
+        let Some(instruction) = self.instructions.get(self.log.len()) else { return false };
+        // the crane moves as many crates as there are
+        let moved = instruction.qty.min(self.stacks[instruction.origin - 1].len());
+        self.crane.apply(&mut self.stacks, instruction);
+        self.log.push(Undo { origin: instruction.origin, dest: instruction.dest, moved });
+        true
+    }
+}
+
+impl Animate for Replay<'_> {
+    fn step(&mut self) -> bool {
+        self.forward()
+    }
+
+    /// Number of steps taken
+    fn position(&self) -> Option<usize> {
+        Some(self.log.len())
+    }
+
+    /// Has the crane move back the crates the last step moved
+    fn back(&mut self) -> bool {
+        let Some(undo) = self.log.pop() else { return false };
+        self.crane.undo(&mut self.stacks, &undo.instruction());
+        true
+    }
+
+    /// The drawing, tall enough for every crate on one stack, with the crates just moved
+    /// highlighted
+    fn render(&self) -> Frame {
+        let total: usize = self.stacks.iter().map(Vec::len).sum();
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        let mut frame = Frame::from_text(&("\n".repeat(total - height) + &drawing(&self.stacks)));
+        // a crate's row counting down from the top of the frame, and its column
+        let pos = |n: usize, level: usize| (total - 1 - level, 4 * (n - 1) + 1);
+
+        let step = self.log.len();
+        frame.caption = format!("crane {}, step {step} of {}", self.crane, self.instructions.len());
+        if let (Some(instruction), Some(&undo)) = (self.instructions.get(step.wrapping_sub(1)), self.log.last()) {
+            let dest = &self.stacks[instruction.dest - 1];
+            let mut before = self.stacks.clone();
+            self.crane.undo(&mut before, &undo.instruction());
+            // crates below where the load went down are unchanged
+            let start = dest.iter().zip(&before[instruction.dest - 1]).take_while(|(a, b)| a == b).count();
+            for (level, &c) in dest.iter().enumerate().skip(start).take(undo.moved) {
+                frame.paint(pos(instruction.dest, level), c, Color::Yellow);
+            }
+            if let Some(level) = dest.len().checked_sub(1) {
+                frame.focus = Some(pos(instruction.dest, level));
+            }
+            frame.caption += &format!(": {instruction}");
+        }
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input;
+    use crate::{crane::*, parse, stack_tops};
+    use super::*;
+
+    #[test]
+    fn undo_and_jump() {
+        let (stacks, instructions) = parse(&input::test_input()).unwrap();
+        let mut replay = Replay::new(&stacks, &instructions, &CrateMover9000);
+        assert!(!replay.back());
+        while replay.forward() {}
+        assert_eq!(stack_tops(replay.stacks()), "CMZ");
+
+        replay.jump(1);
+        assert_eq!(replay.position(), Some(1));
+        assert_eq!(replay.stacks(), [vec!['Z', 'N', 'D'], vec!['M', 'C'], vec!['P']]);
+        assert_eq!(replay.log()[0].moved, 1);
+        replay.jump(0);
+        assert_eq!(replay.stacks(), stacks);
+        replay.jump(99);
+        assert_eq!(replay.position(), Some(instructions.len()));
+
+        let frame = replay.render();
+        assert_eq!(frame.caption, "crane 9000, step 4 of 4: move 1 from 1 to 2");
+        assert_eq!(frame.cells.height(), 6 + 1);
+        assert_eq!(frame.cells[frame.focus.unwrap()].ch, 'M');
+    }
+
+    #[test]
+    fn undo_in_place() {
+        let stacks = vec![vec!['A', 'B', 'C']];
+        let instructions = [Instruction { qty: 2, origin: 1, dest: 1 }];
+        let mut replay = Replay::new(&stacks, &instructions, &Inserting { depth: 1 });
+        replay.forward();
+        assert_eq!(replay.stacks(), [vec!['B', 'C', 'A']]);
+        replay.back();
+        assert_eq!(replay.stacks(), stacks);
+    }
+}
@@ -0,0 +1,230 @@
+//! Day 6: Tuning Trouble
+
+use std::{collections::VecDeque, io::{self, BufRead, BufReader, Read}};
+use aoc_common::{parse::ParseError, solution::Solution};
+
+/// The last `size` characters, counted
+#[derive(Debug, Clone)]
+struct Window {
+    size: usize,
+    /// Indexed by character code
+    counts: [u32; 256],
+    /// Characters in the window more than once
+    repeated: usize
+}
+
+/// Finds markers, runs of a given number of distinct characters, for several sizes at once as the
+/// stream is read a character at a time. Each character takes constant time for each size.
+/// Characters are counted by code, so only U+0000 to U+00FF are allowed.
+#[derive(Debug, Clone)]
+pub struct Detector {
+    windows: Vec<Window>,
+    /// As many of the latest characters as the largest window holds, oldest first
+    recent: VecDeque<u8>,
+    /// Characters read so far
+    read: usize
+}
+
+impl Detector {
+    /// Panics if any size is 0
+    pub fn new(sizes: &[usize]) -> Self {
+        assert!(sizes.iter().all(|&size| size > 0), "markers are at least one character long");
+        let windows = sizes.iter().map(|&size| Window { size, counts: [0; 256], repeated: 0 }).collect();
+        Detector { windows, recent: VecDeque::new(), read: 0 }
+    }
+
+    /// Characters read so far
+    pub fn read(&self) -> usize {
+        self.read
+    }
+
+    /// Reads the next character, by its code, returning the index of each size with a marker ending
+    /// on it
+    pub fn push(&mut self, c: u8) -> impl Iterator<Item = usize> + '_ {
+        self.read += 1;
+        self.recent.push_back(c);
+        let largest = self.windows.iter().map(|window| window.size).max().unwrap_or(0);
+        for window in &mut self.windows {
+            let count = &mut window.counts[c as usize];
+            *count += 1;
+            if *count == 2 {
+                window.repeated += 1;
+            }
+            // the character falling out of this window
+            if let Some(&old) = self.recent.len().checked_sub(window.size + 1).and_then(|i| self.recent.get(i)) {
+                let count = &mut window.counts[old as usize];
+                *count -= 1;
+                if *count == 1 {
+                    window.repeated -= 1;
+                }
+            }
+        }
+        if self.recent.len() > largest {
+            self.recent.pop_front();
+        }
+        let read = self.read;
+        self.windows.iter().enumerate()
+            .filter(move |(_, window)| read >= window.size && window.repeated == 0)
+            .map(|(i, _)| i)
+    }
+}
+
+/// Feeds the code of each character of `stream` to `f`, decoding UTF-8 however it's split between
+/// reads, until `f` returns false. Fails on a character past U+00FF.
+fn each_char(stream: impl Read, mut f: impl FnMut(u8) -> bool) -> io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    // bytes of a character split across reads
+    let mut partial: Vec<u8> = vec![];
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() && partial.is_empty() {
+            return Ok(());
+        } else if buf.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "stream ends part way through a character"));
+        }
+        partial.extend_from_slice(buf);
+        let len = buf.len();
+        reader.consume(len);
+        let valid = match std::str::from_utf8(&partial) {
+            Ok(text) => text,
+            Err(err) if err.error_len().is_none() => std::str::from_utf8(&partial[..err.valid_up_to()]).unwrap(),
+            Err(err) => return Err(io::Error::new(io::ErrorKind::InvalidData, err))
+        };
+        for c in valid.chars() {
+            let code = u8::try_from(c).map_err(|_| io::Error::new(io::ErrorKind::InvalidData, unsupported(c)))?;
+            if !f(code) {
+                return Ok(());
+            }
+        }
+        partial.drain(..valid.len());
+    }
+}
+
+fn unsupported(c: char) -> String {
+    format!("unsupported character {c:?}, expected U+0000 to U+00FF")
+}
+
+/// For each of `sizes`, the number of characters read by the end of every marker of that size in
+/// `stream`, in one pass
+pub fn markers(stream: impl Read, sizes: &[usize]) -> io::Result<Vec<Vec<usize>>> {
+    let mut detector = Detector::new(sizes);
+    let mut found = vec![vec![]; sizes.len()];
+    each_char(stream, |c| {
+        let read = detector.read() + 1;
+        for i in detector.push(c) {
+            found[i].push(read);
+        }
+        true
+    })?;
+    Ok(found)
+}
+
+/// For each of `sizes`, the number of characters read by the end of the first marker of that size,
+/// reading no further than needed
+pub fn first_markers(stream: impl Read, sizes: &[usize]) -> io::Result<Vec<Option<usize>>> {
+    let mut detector = Detector::new(sizes);
+    let mut found = vec![None; sizes.len()];
+    let mut left = sizes.len();
+    each_char(stream, |c| {
+        let read = detector.read() + 1;
+        for i in detector.push(c) {
+            if found[i].is_none() {
+                found[i] = Some(read);
+                left -= 1;
+            }
+        }
+        left > 0
+    })?;
+    Ok(found)
+}
+
+/// Number of characters read by the end of the first `size` distinct characters in a row. Panics
+/// on a character past U+00FF, as `parse` checks for.
+pub fn first_marker(stream: &str, size: usize) -> Option<usize> {
+    first_markers(stream.as_bytes(), &[size]).unwrap_or_else(|err| panic!("{err}"))[0]
+}
+
+/// The datastream buffer, without the final newline, its characters from U+0000 to U+00FF
+pub fn parse(input: &str) -> Result<String, ParseError> {
+    let stream = input.trim_end_matches(['\r', '\n']);
+    if let Some((i, c)) = stream.char_indices().find(|&(_, c)| u8::try_from(c).is_err()) {
+        return Err(ParseError::new(input, &stream[i..i + c.len_utf8()], unsupported(c)));
+    }
+    Ok(stream.to_string())
+}
+
+/// Characters read before the first start-of-packet marker
+pub fn part1(stream: &str) -> Option<usize> {
+    first_marker(stream, 4)
+}
+
+/// Characters read before the first start-of-message marker
+pub fn part2(stream: &str) -> Option<usize> {
+    first_marker(stream, 14)
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    type Parsed = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part1(stream: &Self::Parsed) -> String {
+        part1(stream).map_or("no marker".to_string(), |read| read.to_string())
+    }
+
+    fn part2(stream: &Self::Parsed) -> String {
+        part2(stream).map_or("no marker".to_string(), |read| read.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::examples;
+    use crate::*;
+
+    #[test]
+    fn readme_examples() {
+        examples::check::<Day6>();
+    }
+
+    /// Hands out a byte per read, splitting multi-byte characters
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.0.len().min(buf.len()).min(1);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn markers_in_one_pass() {
+        // the only marker is the last window
+        assert_eq!(first_marker("aabcd", 4), Some(5));
+        assert_eq!(first_marker("abcabc", 4), None);
+        assert_eq!(first_marker("ab", 4), None);
+
+        let stream = "aébéçdaa";
+        assert_eq!(first_markers(Trickle(stream.as_bytes()), &[2, 3, 4, 9]).unwrap(), [Some(2), Some(3), Some(6), None]);
+        assert_eq!(markers(Trickle(stream.as_bytes()), &[3, 1]).unwrap(),
+                   [vec![3, 5, 6, 7], (1..=8).collect()]);
+        assert!(markers(&b"ab\xc3"[..], &[2]).is_err());
+        assert!(markers(&b"ab\xffcd"[..], &[2]).is_err());
+    }
+
+    #[test]
+    fn unsupported_characters() {
+        assert!(markers("ab€c".as_bytes(), &[2]).is_err());
+        // stops reading before it
+        assert_eq!(first_markers("ab€c".as_bytes(), &[2]).unwrap(), [Some(2)]);
+        let err = parse("abcd\nab€c\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "€"));
+    }
+}
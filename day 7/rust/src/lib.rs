@@ -0,0 +1,171 @@
+//! Day 7: No Space Left On Device
+
+use std::{fmt::{self, Debug, Display}, str::FromStr};
+use aoc_common::{parse::{self, ParseError}, solution::Solution};
+use regex::Regex;
+
+#[macro_use]
+extern crate lazy_static;
+
+pub mod tree;
+use tree::INIT_CHILD_NODE_CAP;
+pub use tree::{FlatTree, Node};
+
+/// A file, or a directory once its total size has been calculated
+#[derive(Debug)]
+pub struct FileData {
+    pub size: Option<usize>,
+    pub is_dir: bool
+}
+
+impl Display for Node<FileData> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.data.is_dir {
+            false => write!(f, "{} (file, size={:?})", self.name, self.data.size.unwrap()),
+            true  => match self.data.size {
+                Some(s) => write!(f, "{} (dir, total size={:?})", self.name, s),
+                None => write!(f, "{} (dir)", self.name),
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Input {
+    Cd{to_dir:String},
+    Ls,
+    ListedDir{name: String},
+    ListedFile{name: String, size: usize}
+}
+
+impl FromStr for Input {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE_CD: Regex   = Regex::new(r"\$ cd (.*)").unwrap();
+            static ref RE_DIR: Regex  = Regex::new(r"dir (.*)").unwrap();
+            static ref RE_FILE: Regex = Regex::new(r"(\S*) (.*)").unwrap();
+        }
+
+        if s==r"$ ls" {
+            return Ok( Input::Ls );
+        } else if let Some(cap) = RE_CD.captures(s) {
+            return Ok( Input::Cd { to_dir: cap.get(1).unwrap().as_str().to_string() } );
+        } else if let Some(cap) = RE_DIR.captures(s) {
+            return Ok( Input::ListedDir { name: cap.get(1).unwrap().as_str().to_string() } );
+        } else if let Some(cap) = RE_FILE.captures(s) {
+            return Ok( Input::ListedFile { name: cap.get(2).unwrap().as_str().to_string(), 
+                                           size: parse::field(s, cap.get(1).unwrap().as_str())? } );
+        }
+        Err( ParseError::new(s, s, "expected a command, dir or file listing") )
+    }
+}
+
+impl FlatTree<FileData> {
+    fn _ls(&self, idx: usize) {
+        self._print_children(idx, "  ".to_string(), 0, 3);
+    }
+
+    fn parse_lines(&mut self, contents: &str) -> Result<(), ParseError> {
+        for (i, line) in contents.lines().enumerate() {
+            match line.parse::<Input>().map_err(|err| err.offset_lines(i))? {
+                Input::ListedDir { name } => self.new_here(name, FileData {size: None, is_dir: true}),
+                Input::ListedFile { name, size } => self.new_here(name, FileData {size: Some(size), is_dir: false}),
+                Input::Cd { to_dir } => {
+                    if to_dir=="/" {
+                        self.traverse_to_root()
+                    } else if to_dir==".." {
+                        self.traverse_up()
+                    } else {
+                        self.traverse_into(to_dir)
+                    }
+                },
+                _ => ()
+            }
+        }
+        Ok(())
+    }
+
+    fn try_calc_size(&mut self, idx: usize) {
+        if self.nodes[idx].data.size.is_some() {
+            return; // bail if already calculated
+        }
+        let mut sum = 0;
+        let mut failed_at: Vec<usize> = Vec::with_capacity(INIT_CHILD_NODE_CAP);
+        for ci in self.nodes[idx].children.iter() {
+            if self.nodes[*ci].data.size.is_none() {
+                failed_at.push(*ci);
+            } else {
+                sum += self.nodes[*ci].data.size.unwrap();
+            }
+        }
+        if !failed_at.is_empty() {
+            for ci in failed_at {
+                self.try_calc_size(ci);
+            }
+            self.try_calc_size(idx); // try again, could probably keep working on partial sum?
+        } else {
+            self.nodes[idx].data.size = Some(sum);
+        }
+    }
+
+    fn from_file(contents: &str) -> Result<FlatTree<FileData>, ParseError> {
+        let n = contents.lines().count();
+    
+        let mut tree = FlatTree::<FileData>::new(n); // should initialise with root?
+        tree.new_node("/".to_string(), FileData {size: None, is_dir: true}, None);
+        tree.parse_lines(contents)?;
+    
+        // calc dir sizes
+        let n = tree.nodes.len();
+        for idx in (0..n).rev() { // calc sizes for (heuristically) outermost dirs first
+            tree.try_calc_size(idx);
+        }
+    
+        Ok(tree)
+    }
+
+}
+
+/// The filesystem explored by the terminal output, with every directory's total size
+pub fn parse(input: &str) -> Result<FlatTree<FileData>, ParseError> {
+    FlatTree::<FileData>::from_file(input)
+}
+
+/// Sum of the sizes of directories of at most 100000
+pub fn part1(tree: &FlatTree<FileData>) -> usize {
+    tree.nodes.iter().filter(|n| n.data.is_dir)
+                     .map(|n| n.data.size.unwrap())
+                     .filter(|s| *s<=100_000).sum()
+}
+
+static TOTAL_CAPACITY:usize = 70000000;
+static NEEDED_FREE:usize    = 30000000;
+
+/// Size of the smallest directory which would free up enough space
+pub fn part2(tree: &FlatTree<FileData>) -> usize {
+    let req_cap = TOTAL_CAPACITY - NEEDED_FREE;
+    let min_to_free = tree.nodes[0].data.size.unwrap() - req_cap;
+    let ans = tree.nodes.iter().filter(|n| n.data.is_dir && n.data.size.unwrap()>min_to_free)
+                                                        .min_by_key(|n| n.data.size.unwrap()).unwrap();
+    ans.data.size.unwrap()
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    type Parsed = FlatTree<FileData>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part1(tree: &Self::Parsed) -> String {
+        part1(tree).to_string()
+    }
+
+    fn part2(tree: &Self::Parsed) -> String {
+        part2(tree).to_string()
+    }
+}
@@ -1,16 +1,18 @@
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 
 pub static INIT_CHILD_NODE_CAP: usize = 8;
 
+/// A node of a `FlatTree`, linked to others by their index
 #[derive(Debug)]
 pub struct Node<T: Debug> {
-    pub idx: usize, // will change if nodes removed, not needed if not backtracking?
     pub parent: Option<usize>,
     pub name: String,
     pub data: T,
     pub children: Vec<usize>
 }
 
+/// Tree stored as a vec of nodes, with a cursor at the `current` node for building it.
+/// The first node added is the root.
 #[derive(Debug)]
 pub struct FlatTree<T: Debug> {
     pub nodes: Vec<Node<T>>,
@@ -30,7 +32,7 @@ impl<T: Debug> FlatTree<T> {
             self.nodes[pidx].children.push(idx)
         }
         let children: Vec<usize> = Vec::with_capacity(INIT_CHILD_NODE_CAP);
-        self.nodes.push( Node {idx, parent, name, data, children} );
+        self.nodes.push( Node {parent, name, data, children} );
     }
 
     pub fn new_here(&mut self, name: String, data: T, ) {
@@ -56,17 +58,17 @@ impl<T: Debug> FlatTree<T> {
         }
     }
 
-    pub fn to_root(&mut self) {
-        if self.nodes.len()>0 {
+    pub fn traverse_to_root(&mut self) {
+        if !self.nodes.is_empty() {
             self.current = Some(0)
         }
     }
 }
 
-impl<T: Debug> FlatTree<T> where Node<T> : ToString {
+impl<T: Debug> FlatTree<T> where Node<T> : Display {
     pub fn _print_children(&self, idx: usize, prefix: String, call_count: usize, max_call_count: usize) {
         if call_count<max_call_count {
-            println!("{}{}", prefix.repeat(call_count), &self.nodes[idx].to_string());
+            println!("{}{}", prefix.repeat(call_count), &self.nodes[idx]);
             for &i in &self.nodes[idx].children {
                 self._print_children(i, prefix.clone(), call_count+1, max_call_count)
             }
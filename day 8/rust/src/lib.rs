@@ -0,0 +1,148 @@
+//! Day 8: Treetop Tree House
+
+use std::str::FromStr;
+use aoc_common::{grid::{self, Grid2D, Pos, Step}, parse::ParseError, solution::Solution};
+
+#[derive(Clone, Copy, Debug)]
+pub enum CompassDirection {
+    North,
+    East,
+    South,
+    West
+}
+use CompassDirection::{North, East, South, West};
+
+impl CompassDirection {
+    pub fn iter() -> impl Iterator<Item=CompassDirection> {
+        [North, East, South, West].iter().copied()
+    }
+
+    pub fn step(self) -> Step {
+        match self {
+            North => grid::UP,
+            East => grid::RIGHT,
+            South => grid::DOWN,
+            West => grid::LEFT
+        }
+    }
+}
+
+/// Grid of tree heights, 0 to 9
+#[derive(Debug)]
+pub struct Forest {
+    pub heights: Grid2D<u8>
+}
+
+impl FromStr for Forest {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let heights = Grid2D::parse(s, |_, c| c.to_digit(10).map(|h| h as u8).ok_or("expected a tree height 0-9"))?;
+        Ok( Forest {heights} )
+    }
+}
+
+/// What can be seen looking out from a tree in one direction
+pub struct Ray {
+    /// The first tree at least as high, if any before the edge
+    pub blocking_tree: Option<Pos>,
+    /// Number of trees seen, including the blocking tree
+    pub dist: usize
+}
+
+impl Forest {
+    pub fn cast_ray(&self, pos: Pos, dir: CompassDirection) -> Ray {
+        let mut dist: usize = 0;
+        let blocking_tree = self.heights.ray(pos, dir.step()).find(|&p| {
+            dist+=1;
+            self.heights[p]>=self.heights[pos]
+        });
+        Ray {blocking_tree, dist}
+    }
+
+    /// Whether the tree at `pos` can be seen from outside the grid in direction `dir`
+    pub fn visible_from(&self, pos: Pos, dir: CompassDirection) -> bool {
+        self.cast_ray(pos, dir).blocking_tree.is_none()
+    }
+
+    pub fn visible(&self, pos: Pos) -> bool {
+        CompassDirection::iter().any(|dir| self.visible_from(pos, dir))
+    }
+
+    // part 1 sol
+    pub fn num_visible(&self) -> usize {
+        self.heights.positions().filter(|&pos| self.visible(pos)).count()
+    }
+
+    /// Product of the viewing distances in each direction
+    pub fn view_score(&self, pos: Pos) -> usize {
+        CompassDirection::iter().map(|dir| self.cast_ray(pos, dir).dist).product()
+    }
+
+    // part 2 sol
+    pub fn best_view(&self) -> usize {
+        self.heights.positions().map(|pos| self.view_score(pos)).max().unwrap()
+    }
+
+}
+
+pub fn parse(input: &str) -> Result<Forest, ParseError> {
+    input.parse()
+}
+
+/// Number of trees visible from outside the grid
+pub fn part1(forest: &Forest) -> usize {
+    forest.num_visible()
+}
+
+/// Highest scenic score of any tree
+pub fn part2(forest: &Forest) -> usize {
+    forest.best_view()
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    type Parsed = Forest;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part1(forest: &Self::Parsed) -> String {
+        part1(forest).to_string()
+    }
+
+    fn part2(forest: &Self::Parsed) -> String {
+        // let pos = (3,2);
+        // for dir in CompassDirection::iter() {
+        //     let Ray {blocking_tree, dist} = forest.cast_ray(pos, dir);
+        //     println!("{:?} {:?} {:?}", dir, blocking_tree, dist);
+        // }
+        // dbg!(forest.view_score(pos));
+
+        part2(forest).to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::{examples, input};
+    use crate::*;
+
+    #[test]
+    fn readme_examples() {
+        examples::check::<Day8>();
+    }
+
+    #[test]
+    fn sanity_check() {
+        let tcontents = input::test_input();
+        let forest: Forest = tcontents.parse().unwrap();
+
+        let expected = [true, false, false, true];
+        for (i, dir) in CompassDirection::iter().enumerate() {
+            assert_eq!(forest.visible_from((1,1), dir), expected[i]);
+        }
+    }
+}
@@ -0,0 +1,238 @@
+//! Day 9: Rope Bridge
+
+use std::{str::FromStr, fmt::{self, Display}, collections::HashSet};
+use aoc_common::{animation::{Animate, Color, Frame}, parse::{self, ParseError}, point::Point, solution::Solution};
+
+/// A rope of knots each following the one in front, with y increasing upwards
+#[derive(Debug)]
+pub struct RopeSim {
+    pub head: Point,
+    /// The knots behind the head, in order
+    pub tail: Vec<Point>,
+    /// Every position visited by the last knot
+    pub tail_trace: HashSet<Point>
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down
+}
+use Direction::*;
+
+impl TryFrom<char> for Direction {
+    type Error = String;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'L' => Ok(Left),
+            'R' => Ok(Right),
+            'D' => Ok(Down),
+            'U' => Ok(Up),
+             _  => Err("Not a direction".to_string())
+        }
+    }
+}
+
+impl From<Direction> for char {
+    fn from(value: Direction) -> Self {
+        match value {
+            Left  => 'L',
+            Right => 'R',
+            Down  => 'D',
+            Up    => 'U'
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c: char = (*self).into();
+        write!(f, "{c}")
+    }
+}
+
+/// Move the head `amount` steps in `dir`
+#[derive(Debug)]
+pub struct Instruction {
+    pub dir: Direction,
+    pub amount: usize
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (dir_str, amount_str) = s.split_once(' ')
+            .ok_or_else(|| ParseError::new(s, s, "expected \"<direction> <amount>\""))?;
+        let mut dir_chars = dir_str.chars();
+        let dir: Direction = match (dir_chars.next(), dir_chars.next()) {
+            (Some(c), None) => c.try_into().map_err(|err| ParseError::new(s, dir_str, err))?,
+            _ => return Err( ParseError::new(s, dir_str, "Not a direction") )
+        };
+        let amount: usize = parse::field(s, amount_str)?;
+        Ok( Instruction { dir, amount } )
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.dir, self.amount)
+    }
+}
+
+fn inc_pos(Point {x, y}: Point, dir: Direction) -> Point {
+    match dir {
+        Left  => Point::new(x-1, y),
+        Right => Point::new(x+1, y),
+        Up    => Point::new(x, y+1),
+        Down  => Point::new(x, y-1)
+    }
+}
+
+/// Where `tail` moves to keep up with `head`
+pub fn follow(head: Point, tail: Point) -> Point {
+    let (dx, dy) = (head.x-tail.x, head.y-tail.y);
+    if dx.abs()>1 && dy.abs()==0 {        // same row
+        Point::new(tail.x + dx.signum(), tail.y)
+    } else if dx.abs()==0 && dy.abs()>1 { // same column
+        Point::new(tail.x, tail.y + dy.signum())
+    } else if  dx.abs()>1 && dy.abs()>=1  // knights move
+           ||  dy.abs()>1 && dx.abs()>=1 {
+        Point::new(tail.x + dx.signum(), tail.y + dy.signum())
+    } else {
+        tail
+    }
+}
+
+static KNOT_SYMBOLS : &str = "123456789";
+
+impl RopeSim {
+    /// A rope with `length` knots behind the head, all starting at the origin
+    pub fn new(length: usize) -> Self {
+        let tail = vec![Point::new(0,0); length];
+        RopeSim { head:Point::new(0,0), tail, tail_trace:HashSet::new()}
+    }
+
+    /// Bounding box (bottom left, top right) of the rope and everywhere its last knot has been
+    pub fn extents(&self) -> (Point, Point) {
+        self.tail_trace.iter().chain(self.tail.iter())
+            .fold((self.head, self.head), |(bl, tr), p| (bl.bb_tl(*p), tr.bb_br(*p)))
+    }
+
+    pub fn step(&mut self, dir: Direction) {
+        self.head = inc_pos(self.head, dir);
+        
+        self.tail[0] = follow(self.head, self.tail[0]);
+        for i in 1..self.tail.len() {
+            self.tail[i] = follow(self.tail[i-1], self.tail[i]);
+        }
+
+        self.tail_trace.insert(*self.tail.last().unwrap());
+    }
+
+    /// Moves the head through every instruction
+    pub fn play(&mut self, ins: &[Instruction]) {
+        for &Instruction {dir, amount} in ins {
+            for _ in 0..amount {
+                self.step(dir);
+            }
+        }
+    }
+}
+
+/// A rope following the instructions a step of the head at a time, leaving a trail where its
+/// last knot has been
+pub struct RopeAnimation<'a> {
+    pub sim: RopeSim,
+    ins: &'a [Instruction],
+    /// Instruction in progress, and steps of it taken
+    current: usize,
+    taken: usize
+}
+
+impl<'a> RopeAnimation<'a> {
+    pub fn new(length: usize, ins: &'a [Instruction]) -> Self {
+        RopeAnimation { sim: RopeSim::new(length), ins, current: 0, taken: 0 }
+    }
+}
+
+impl Animate for RopeAnimation<'_> {
+    fn step(&mut self) -> bool {
+        while self.current < self.ins.len() && self.taken == self.ins[self.current].amount {
+            self.current += 1;
+            self.taken = 0;
+        }
+        let Some(instruction) = self.ins.get(self.current) else { return false };
+        self.sim.step(instruction.dir);
+        self.taken += 1;
+        true
+    }
+
+    fn render(&self) -> Frame {
+        let (bl, tr) = self.sim.extents();
+        // y increases upwards, rows downwards
+        let pos = |p: Point| ((tr.y - p.y) as usize, (p.x - bl.x) as usize);
+        let mut frame = Frame::new((tr.y - bl.y + 1) as usize, (tr.x - bl.x + 1) as usize);
+        for &p in &self.sim.tail_trace {
+            frame.paint(pos(p), '#', Color::DarkGrey);
+        }
+        for (k, &p) in self.sim.tail.iter().enumerate().rev() {
+            frame.paint(pos(p), KNOT_SYMBOLS.chars().nth(k).unwrap_or('*'), Color::Yellow);
+        }
+        frame.paint(pos(self.sim.head), 'H', Color::Red);
+        frame.focus = Some(pos(self.sim.head));
+        let visited = format!("{} positions visited", self.sim.tail_trace.len());
+        frame.caption = match self.ins.get(self.current) {
+            Some(instruction) => format!("== {instruction} ==  {visited}"),
+            None => visited
+        };
+        frame
+    }
+}
+
+/// Number of positions visited by the last knot of a rope with `length` knots following the head
+pub fn tail_visits(ins: &[Instruction], length: usize) -> usize {
+    let mut rope_sim = RopeSim::new(length);
+    rope_sim.play(ins);
+    rope_sim.tail_trace.len()
+}
+
+pub fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse::lines(input)
+}
+
+/// Positions visited by the tail of a rope with 2 knots
+pub fn part1(ins: &[Instruction]) -> usize {
+    tail_visits(ins, 1)
+}
+
+/// Positions visited by the tail of a rope with 10 knots
+pub fn part2(ins: &[Instruction]) -> usize {
+    tail_visits(ins, 9)
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    type Parsed = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part1(ins: &Self::Parsed) -> String {
+        part1(ins).to_string()
+    }
+
+    fn part2(ins: &Self::Parsed) -> String {
+        part2(ins).to_string()
+    }
+
+    fn animations(ins: &Self::Parsed) -> Vec<Box<dyn Animate + '_>> {
+        vec![Box::new(RopeAnimation::new(9, ins))]
+    }
+}